@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::fs::{read_to_string, write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+// How often the limits are checked
+const TICK: Duration = Duration::from_millis(50);
+
+// The stages of the algorithm, in the order in which they run
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Stage {
+    /// Simplification by iterated Čech nerves
+    Nerve,
+    /// Edge pinching
+    Pinch,
+    /// Accretion of the contractible (or acyclic) subcomplex
+    Accretion,
+    /// Minimization of the output pair
+    Minimize,
+    /// Iterated reduction of the output pair
+    ReducePair,
+}
+
+impl Stage {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Nerve => "Reduction by Čech nerves",
+            Self::Pinch => "Pinching",
+            Self::Accretion => "Accretion",
+            Self::Minimize => "Minimization of the pair",
+            Self::ReducePair => "Reduction of the pair",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct Limits {
+    pub time: Option<Duration>,
+    // In kibibytes
+    pub memory: Option<u64>,
+}
+
+impl Limits {
+    pub fn is_empty(&self) -> bool {
+        self.time.is_none() && self.memory.is_none()
+    }
+}
+
+// The reason a stage was cut short
+#[derive(Clone, Copy)]
+pub enum Cut {
+    Interrupted,
+    TimeLimit(Duration),
+    MemoryLimit(u64),
+    StageTimeLimit(Duration),
+    StageMemoryLimit(u64),
+}
+
+impl Cut {
+    pub fn explanation(&self) -> String {
+        match self {
+            Self::Interrupted => "the program was interrupted".to_string(),
+            Self::TimeLimit(t) => format!["the time limit of {}s was reached", t.as_secs()],
+            Self::MemoryLimit(m) => format!["the memory limit of {}MB was reached", m / 1024],
+            Self::StageTimeLimit(t) => {
+                format!["the stage's time limit of {}s was reached", t.as_secs()]
+            }
+            Self::StageMemoryLimit(m) => {
+                format!["the stage's memory limit of {}MB was reached", m / 1024]
+            }
+        }
+    }
+}
+
+struct Watch {
+    start: Instant,
+    limits: Limits,
+    stop: Arc<AtomicBool>,
+    cut: Option<Cut>,
+}
+
+// Enforces time and memory limits on the whole run and on individual stages. A watchdog thread
+// raises the current stage's stop flag when a limit is exceeded or the program is interrupted;
+// exceeding a global limit also raises `interrupted`, which stops every later stage. Without
+// limits, the budget is inert: there is no watchdog, and each stage stops when `interrupted` is
+// raised.
+pub struct Budget {
+    stage_limits: HashMap<Stage, Limits>,
+    // The stage being watched, or `None` if the budget is inert
+    current: Option<Arc<Mutex<Option<Watch>>>>,
+    interrupted: Arc<AtomicBool>,
+}
+
+impl Budget {
+    pub fn start(
+        global: Limits,
+        stage_limits: HashMap<Stage, Limits>,
+        interrupted: Arc<AtomicBool>,
+    ) -> Self {
+        if global.is_empty() && stage_limits.values().all(Limits::is_empty) {
+            return Self {
+                stage_limits,
+                current: None,
+                interrupted,
+            };
+        }
+
+        let start = Instant::now();
+        let current = Arc::new(Mutex::new(None::<Watch>));
+        let watched = current.clone();
+        let measure_memory =
+            global.memory.is_some() || stage_limits.values().any(|l| l.memory.is_some());
+        let watchdog_interrupted = interrupted.clone();
+
+        thread::spawn(move || {
+            let interrupted = watchdog_interrupted;
+            loop {
+                thread::sleep(TICK);
+                let memory = if measure_memory {
+                    resident_memory()
+                } else {
+                    None
+                };
+                let global_cut = if interrupted.load(Ordering::Relaxed) {
+                    Some(Cut::Interrupted)
+                } else if let Some(t) = global.time.filter(|t| start.elapsed() > *t) {
+                    Some(Cut::TimeLimit(t))
+                } else {
+                    global
+                        .memory
+                        .filter(|m| memory.is_some_and(|r| r > *m))
+                        .map(Cut::MemoryLimit)
+                };
+                if global_cut.is_some() {
+                    interrupted.store(true, Ordering::SeqCst);
+                }
+
+                let mut current = watched.lock().unwrap();
+                let Some(watch) = current.as_mut() else {
+                    continue;
+                };
+                if watch.cut.is_some() {
+                    continue;
+                }
+                watch.cut = global_cut.or_else(|| {
+                    if let Some(t) = watch.limits.time.filter(|t| watch.start.elapsed() > *t) {
+                        Some(Cut::StageTimeLimit(t))
+                    } else {
+                        watch
+                            .limits
+                            .memory
+                            .filter(|m| memory.is_some_and(|r| r > *m))
+                            .map(Cut::StageMemoryLimit)
+                    }
+                });
+                if watch.cut.is_some() {
+                    watch.stop.store(true, Ordering::SeqCst);
+                }
+            }
+        });
+
+        Self {
+            stage_limits,
+            current: Some(current),
+            interrupted,
+        }
+    }
+
+    // Start watching a stage. The returned flag is raised when the stage should stop.
+    pub fn enter(&self, stage: Stage) -> Arc<AtomicBool> {
+        let Some(current) = &self.current else {
+            return self.interrupted.clone();
+        };
+        let stop = Arc::new(AtomicBool::new(false));
+        *current.lock().unwrap() = Some(Watch {
+            start: Instant::now(),
+            limits: self.stage_limits.get(&stage).copied().unwrap_or_default(),
+            stop: stop.clone(),
+            cut: None,
+        });
+
+        stop
+    }
+
+    // Stop watching the current stage and return the reason it was cut short, if it was.
+    pub fn leave(&self) -> Option<Cut> {
+        match &self.current {
+            Some(current) => current.lock().unwrap().take().and_then(|w| w.cut),
+            None => self
+                .interrupted
+                .load(Ordering::Relaxed)
+                .then_some(Cut::Interrupted),
+        }
+    }
+}
+
+// The resident set size of this process in kibibytes, if it can be determined
+pub fn resident_memory() -> Option<u64> {
+    read_status("VmRSS:")
+}
+
+// The peak resident set size of this process in kibibytes since it started or since the peak was
+// last reset, if it can be determined
+pub fn peak_memory() -> Option<u64> {
+    read_status("VmHWM:")
+}
+
+// Reset the peak resident set size to the current resident set size, if possible.
+pub fn reset_peak_memory() {
+    let _ = write("/proc/self/clear_refs", "5");
+}
+
+fn read_status(field: &str) -> Option<u64> {
+    read_to_string("/proc/self/status")
+        .ok()?
+        .lines()
+        .find_map(|l| l.strip_prefix(field))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()
+}
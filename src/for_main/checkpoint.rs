@@ -0,0 +1,203 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use clap::ValueEnum;
+
+use super::budget::Stage;
+use sc_simplify::io::{convert_facets, max_label, parse_facet, sc_info, write_sc_to};
+use sc_simplify::{Face, SimplicialComplex, Vertex};
+
+const HEADER: &str = "sc-simplify checkpoint";
+
+// The state of an interrupted run: the first stage which was not completed and the complexes it
+// was working on. Before accretion, there is no subcomplex. During accretion and minimization,
+// `complex` holds the facets which have not been accreted onto `subcomplex`. Afterwards, the two
+// form the output pair.
+pub struct Checkpoint<Point: Vertex> {
+    pub stage: Stage,
+    // The number of runs of the pinch algorithm left
+    pub pinch_loops: usize,
+    pub acyclic: Option<u32>,
+    pub complex: SimplicialComplex<Point>,
+    pub subcomplex: Option<SimplicialComplex<Point>>,
+}
+
+pub enum AnyCheckpoint {
+    Small(Checkpoint<u16>),
+    Large(Checkpoint<u32>),
+}
+
+impl<Point: Vertex> Checkpoint<Point> {
+    // The state of a run which has not started yet
+    pub fn start(
+        complex: SimplicialComplex<Point>,
+        pinch_loops: usize,
+        acyclic: Option<u32>,
+    ) -> Self {
+        Self {
+            stage: Stage::Nerve,
+            pinch_loops,
+            acyclic,
+            complex,
+            subcomplex: None,
+        }
+    }
+
+    // The checkpoint is a header of `key value` lines followed by the facets of the complex and
+    // the subcomplex in the same format as the input.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln![out, "{HEADER}"]?;
+        writeln![
+            out,
+            "stage {}",
+            self.stage.to_possible_value().unwrap().get_name()
+        ]?;
+        writeln![out, "pinch-loops {}", self.pinch_loops]?;
+        if let Some(p) = self.acyclic {
+            writeln![out, "acyclic {p}"]?;
+        }
+        writeln![out, "complex"]?;
+        write_sc_to(&self.complex, &mut out)?;
+        if let Some(sub) = &self.subcomplex {
+            writeln![out, "subcomplex"]?;
+            write_sc_to(sub, &mut out)?;
+        }
+
+        out.flush()
+    }
+
+    // Why a run with these options cannot resume from the checkpoint, if it cannot. Once
+    // accretion has begun, the run must print a pair and keeps the kind of subcomplex it started
+    // with, whose field `--verify` must use.
+    pub fn conflict(&self, no_pair: bool, verify: Option<u32>) -> Option<String> {
+        self.subcomplex.as_ref()?;
+        if no_pair {
+            return Some(
+                "the argument '--no-pair' cannot be used to resume a run which was interrupted \
+                 after accretion began"
+                    .to_string(),
+            );
+        }
+        match (self.acyclic, verify) {
+            (Some(p), Some(q)) if p != q => Some(format![
+                "the argument '--verify {q}' cannot be used to resume a run whose subcomplex is \
+                 acyclic over the field with {p} elements"
+            ]),
+            _ => None,
+        }
+    }
+
+    fn from_facets(
+        stage: Stage,
+        pinch_loops: usize,
+        acyclic: Option<u32>,
+        complex: Vec<Face<u32>>,
+        subcomplex: Option<Vec<Face<u32>>>,
+    ) -> Self {
+        Self {
+            stage,
+            pinch_loops,
+            acyclic,
+            complex: to_complex(complex),
+            subcomplex: subcomplex.map(to_complex),
+        }
+    }
+
+    fn info(&self) {
+        sc_info(&self.complex, "The resumed complex");
+        if let Some(sub) = &self.subcomplex {
+            sc_info(sub, "The resumed subcomplex");
+        }
+    }
+}
+
+fn to_complex<Point: Vertex>(facets: Vec<Face<u32>>) -> SimplicialComplex<Point> {
+    if facets.is_empty() {
+        SimplicialComplex::default()
+    } else {
+        SimplicialComplex::from_check_sorted(convert_facets(facets))
+    }
+}
+
+pub fn read_checkpoint(path: &Path, quiet: bool) -> AnyCheckpoint {
+    let file = File::open(path).expect("The checkpoint could not be opened.");
+    let mut lines = BufReader::new(file)
+        .lines()
+        .map(|l| l.expect("The checkpoint could not be read."));
+    assert![
+        lines.next().as_deref() == Some(HEADER),
+        "The file is not a checkpoint."
+    ];
+
+    let mut stage = None;
+    let mut pinch_loops = 0;
+    let mut acyclic = None;
+    let mut in_complex = false;
+    let mut complex = Vec::<Face<u32>>::new();
+    let mut subcomplex = None::<Vec<Face<u32>>>;
+    for line in lines {
+        match line.as_str() {
+            "complex" => in_complex = true,
+            "subcomplex" => subcomplex = Some(Vec::new()),
+            l if !in_complex => {
+                let (key, value) = l
+                    .split_once(' ')
+                    .expect("The header of the checkpoint is malformed.");
+                match key {
+                    "stage" => {
+                        stage = Some(
+                            Stage::from_str(value, true)
+                                .expect("The checkpoint names an unknown stage."),
+                        )
+                    }
+                    "pinch-loops" => {
+                        pinch_loops = value
+                            .parse()
+                            .expect("The number of pinch runs should be a natural number.")
+                    }
+                    "acyclic" => {
+                        acyclic = Some(value.parse().expect("The modulus should be a prime."))
+                    }
+                    _ => panic!["The checkpoint has an unknown field `{key}`."],
+                }
+            }
+            l => {
+                let facet = parse_facet(l);
+                if !facet.is_empty() {
+                    subcomplex.as_mut().unwrap_or(&mut complex).push(facet);
+                }
+            }
+        }
+    }
+    let stage = stage.expect("The checkpoint does not name a stage.");
+
+    let max = max_label(&complex).max(subcomplex.as_deref().map_or(0, max_label));
+    let checkpoint = if max > u16::MAX.into() {
+        AnyCheckpoint::Large(Checkpoint::from_facets(
+            stage,
+            pinch_loops,
+            acyclic,
+            complex,
+            subcomplex,
+        ))
+    } else {
+        AnyCheckpoint::Small(Checkpoint::from_facets(
+            stage,
+            pinch_loops,
+            acyclic,
+            complex,
+            subcomplex,
+        ))
+    };
+
+    if !quiet {
+        match &checkpoint {
+            AnyCheckpoint::Small(c) => c.info(),
+            AnyCheckpoint::Large(c) => c.info(),
+        }
+    }
+
+    checkpoint
+}
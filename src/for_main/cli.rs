@@ -1,23 +1,236 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{Args, CommandFactory, FromArgMatches, Subcommand, ValueEnum};
+
+use super::budget::{Limits, Stage};
 use crate::Parser;
+use sc_simplify::SubcomplexStrategy;
+use sc_simplify::homology::is_prime;
 
 #[derive(Parser)]
 #[command(version)]
 /// Read a simplicial complex from stdin and print an equivalent simplified complex or pair.
 ///
 /// Each line of the input is one facet, represented as a space-separated list of vertices.
-/// The vertices should be labeled by natural numbers less than 2^32.
+/// The vertices should be labeled by natural numbers less than 2^32. Complexes saved by GAP's
+/// `simpcomp` package with `SCSaveXML` are also accepted.
+///
+/// Lines such as `(0 1 2)(3 4)` give permutations of the vertices in cycle notation. The complex is
+/// then the union of the orbits of the given facets under the group they generate. It is reduced
+/// with Čech nerves one orbit at a time, and then its orbits are listed: pinching, the
+/// contractible subcomplex and the output work with every facet.
 ///
 /// The default behavior prints a pair X, C of simplicial complexes in the same format as the input
 /// in which X has the homotopy type of the input and C is a large contractible subcomplex of X.
 /// The complexes X and C are delineated by a blank line.
+///
+/// Without a subcommand, `simplify` is run with the options below.
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub simplify: SimplifyArgs,
+
+    #[command(flatten)]
+    pub shared: SharedArgs,
+}
+
+impl Cli {
+    // Parse the command line. The options of `simplify` may precede a subcommand only if the
+    // subcommand is omitted, since they would be ignored otherwise.
+    pub fn parse_args() -> Self {
+        let mut command = Self::command();
+        let matches = command.get_matches_mut();
+        let cli = Self::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+        if let Some((name, _)) = matches.subcommand()
+            && let Some(arg) = SimplifyArgs::augment_args(clap::Command::new("simplify"))
+                .get_arguments()
+                .find(|a| {
+                    matches.value_source(a.get_id().as_str()) == Some(ValueSource::CommandLine)
+                })
+        {
+            command
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    format![
+                        "the argument '{}' cannot be used before the subcommand '{name}'",
+                        arg.get_long()
+                            .map_or_else(|| arg.get_id().to_string(), |l| format!["--{l}"])
+                    ],
+                )
+                .exit();
+        }
+        let simplify = match &cli.command {
+            None => Some(&cli.simplify),
+            Some(Command::Simplify(args)) => Some(&**args),
+            _ => None,
+        };
+        if let Some((kind, message)) = simplify.and_then(|args| args.conflict(&cli.shared)) {
+            command.error(kind, message).exit();
+        }
+
+        cli
+    }
+}
+
+// Exit with a usage error, for options which only turn out to be incompatible once the input has
+// been read
+pub fn usage_error(message: String) -> ! {
+    Cli::command()
+        .error(ErrorKind::ArgumentConflict, message)
+        .exit()
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Print an equivalent simplified complex or pair (the default).
+    Simplify(Box<SimplifyArgs>),
+
+    /// Print the f-vector and other basic invariants of a complex.
+    ///
+    /// The output has one `name value` line for each of the number of vertices, the number of
+    /// facets, the dimension, whether the complex is pure, the number of connected components, the
+    /// Euler characteristic, the f-vector, and the degree distribution of the 1-skeleton as
+    /// `degree:count` pairs. The invariants are computed from the facets without listing every
+    /// face.
+    ///
+    /// For a complex given by orbits of facets, the number of orbits of facets is printed instead
+    /// of the components and degrees, and the faces are counted one orbit at a time.
+    Info,
+
+    /// Print a complex in another format, e.g. `sc-simplify convert --xml`.
+    Convert,
+
+    /// Print the Betti numbers of a complex or pair over the field with P elements.
+    Homology(HomologyArgs),
+
+    /// Print the cohomology ring of a complex over the field with 2 elements.
+    ///
+    /// The first line lists the Betti numbers, starting from degree 0. The `i`-th class of a basis
+    /// of the cohomology in degree `d` is called `xd.i`, and each further line `a * b = c + ...`
+    /// gives a nonzero cup product of classes of positive degree in terms of the basis. The
+    /// products which are not listed vanish.
+    ///
+    /// This tells apart complexes with the same Betti numbers, e.g. the torus, in which the
+    /// product of the two classes of degree 1 is the class of degree 2, from the wedge of two
+    /// circles and a sphere, in which it vanishes.
+    Cohomology(CohomologyArgs),
+
+    /// Print a presentation of the fundamental group of a complex.
+    ///
+    /// The complex is first simplified with Čech nerves and pinching, which preserve the
+    /// fundamental group and keep the presentation small. The edges outside a spanning tree of the
+    /// 1-skeleton generate the group and the 2-faces give the relations, which are then simplified
+    /// with Tietze transformations. The output is a line `generators x1 x2 ...` followed by one
+    /// line `relation w` for each relation, e.g. `relation x1*x2*x1^-1*x2^-1` for the torus. For a
+    /// disconnected complex, this is the fundamental group of the component of the least vertex.
+    FundamentalGroup(FundamentalGroupArgs),
+
+    /// Print the barcode of a filtered complex over the field with P elements.
+    ///
+    /// Each line of the input is a facet followed by the time at which it enters, as in
+    /// `0 1 2 @ 0.5`, and each face enters with the first facet containing it. With `--rips` or
+    /// `--cech`, the filtration of the complex at scale R by the scales at which its faces appear
+    /// is used instead.
+    ///
+    /// The output has one `dimension birth death` line for each bar of positive length, where the
+    /// death of a class which never dies is `inf`.
+    Persistence(PersistenceArgs),
+
+    /// Try to certify that a complex is homotopy equivalent to a wedge of spheres.
+    ///
+    /// The complex is first simplified with Čech nerves and pinching, and then shrunk by
+    /// collapses. If that leaves a vertex, a connected graph or the boundary of a simplex, the
+    /// homotopy type is read off directly. Otherwise, a complex whose fundamental group is shown to
+    /// be trivial by Tietze transformations, and whose reduced integral homology is free and
+    /// concentrated in a single dimension, is a wedge of spheres by the Hurewicz and Whitehead
+    /// theorems.
+    ///
+    /// The output is a line `wedge K D` for a wedge of K spheres of dimension D, where a wedge of
+    /// no spheres is a point, followed by a line `certificate ...` giving the reason. If no
+    /// certificate is found, which does not rule out such a wedge, the output is `unknown`.
+    Recognize(RecognizeArgs),
+
+    /// Print a complex from a family of complexes.
+    #[command(subcommand)]
+    Generate(Generator),
+}
+
+// Options shared by all subcommands
+#[derive(Args)]
+pub struct SharedArgs {
+    /// Read the input from FILE instead of stdin.
+    #[arg(long, global = true, value_name = "FILE")]
+    pub input: Option<PathBuf>,
+
     /// Check that the faces in the input are maximal.
     ///
     /// Depending on other settings used, input including non-maximal faces may cause unexpected
     /// behavior.
-    #[arg(short, long, default_value_t = false)]
+    #[arg(short, long, global = true, default_value_t = false)]
     pub check_input: bool,
 
+    /// Read a graph with one edge `v w` per line, and use its clique complex as the input.
+    ///
+    /// The facets of the clique (flag) complex are the maximal cliques of the graph, which are
+    /// enumerated in parallel and passed on without being printed. A line with a single vertex adds
+    /// an isolated vertex.
+    #[arg(long, global = true, default_value_t = false)]
+    pub graph: bool,
+
+    /// Read a point cloud and use its Vietoris–Rips complex at the scale R as the input.
+    ///
+    /// Each line of the input is a point, given by its coordinates separated by commas or spaces,
+    /// as in a CSV file whose header, if any, is skipped. The faces of the complex are the sets of
+    /// points at Euclidean distance at most R from each other, so its facets are the maximal
+    /// cliques of a graph.
+    #[arg(
+        long,
+        global = true,
+        value_name = "R",
+        group = "metric",
+        conflicts_with = "graph"
+    )]
+    pub rips: Option<f64>,
+
+    /// Read a point cloud and use its Čech complex at the scale R as the input.
+    ///
+    /// The faces of the complex are the sets of points within distance R of a common point of the
+    /// input. Its facets are among the balls of radius R around the points, so its nerve, which is
+    /// taken first when simplifying, is found without computing intersections of balls.
+    #[arg(
+        long,
+        global = true,
+        value_name = "R",
+        group = "metric",
+        conflicts_with = "graph"
+    )]
+    pub cech: Option<f64>,
+
+    /// With `--rips` or `--cech`, read a symmetric matrix of distances rather than a point cloud.
+    #[arg(long, global = true, default_value_t = false, requires = "metric")]
+    pub distance_matrix: bool,
+
+    /// Print complexes in the XML format read by GAP's `simpcomp` package.
+    ///
+    /// Since `simpcomp` does not compute relative homology, this can only be used when printing a
+    /// single complex, e.g. with `--no-pair`.
+    #[arg(short = 'x', long, global = true, default_value_t = false)]
+    pub xml: bool,
+
+    /// Suppress the progress indicators.
+    #[arg(short, long, global = true, default_value_t = false)]
+    pub quiet: bool,
+}
+
+#[derive(Args)]
+pub struct SimplifyArgs {
     /// Skip simplification by iterated Čech nerves.
     ///
     /// The Čech nerve of a complex has the same homotopy type as the complex and roughly exchanges
@@ -65,7 +278,412 @@ pub struct Cli {
     #[arg(short = 'm', long, default_value_t = false)]
     pub skip_minimize_pair: bool,
 
-    /// Suppress the progress indicators.
-    #[arg(short, long, default_value_t = false)]
-    pub quiet: bool,
+    /// The heuristic used to grow the contractible subcomplex.
+    ///
+    /// Different heuristics can yield pairs of very different sizes; none of them is best for
+    /// every input.
+    #[arg(short, long, value_enum, default_value_t = Strategy::Fifo)]
+    pub strategy: Strategy,
+
+    /// Accrete a subcomplex that is only acyclic over the field with P elements.
+    ///
+    /// By default, the subcomplex of the output pair is contractible. If this option is given, it
+    /// is only guaranteed to have trivial reduced homology over the field with P elements, where P
+    /// is prime. This allows many more facets to be accreted, so the output pair is usually much
+    /// smaller, but the pair then only has the same homology as the input over this field rather
+    /// than the same homotopy type.
+    #[arg(short, long, value_name = "P", value_parser = parse_prime)]
+    pub acyclic: Option<u32>,
+
+    /// The number of disjoint seeds grown by the `seeds` strategy.
+    #[arg(long, default_value_t = 4, value_name = "N")]
+    pub seed_count: usize,
+
+    /// Keep reducing the output pair until its number of facets stops decreasing.
+    ///
+    /// The pair is reduced by alternately pinching edges away from the subcomplex, enlarging the
+    /// subcomplex by accretion, and removing the facets shared by the complex and the enlarged
+    /// subcomplex.
+    #[arg(
+        short,
+        long,
+        default_value_t = false,
+        conflicts_with = "skip_minimize_pair"
+    )]
+    pub iterate_pair: bool,
+
+    /// Stop simplifying after this many seconds and print the output.
+    ///
+    /// The stage running when the limit is reached is stopped gracefully, as if the program had
+    /// been interrupted with Ctrl-C, and the remaining stages are skipped.
+    #[arg(long, value_name = "SECS")]
+    pub time_limit: Option<u64>,
+
+    /// Stop simplifying once the program uses this many megabytes of memory and print the output.
+    ///
+    /// The stage running when the limit is reached is stopped gracefully, as if the program had
+    /// been interrupted with Ctrl-C, and the remaining stages are skipped. Memory usage can only
+    /// be measured on Linux.
+    #[arg(long, value_name = "MB")]
+    pub memory_limit: Option<u64>,
+
+    /// Stop a single stage after this many seconds and move on to the next one.
+    ///
+    /// The stages are `nerve`, `pinch`, `accretion`, `minimize`, and `reduce-pair`. This option can
+    /// be given once for each stage, e.g. `--stage-time-limit nerve=600 --stage-time-limit
+    /// pinch=60`.
+    #[arg(long, value_name = "STAGE=SECS", value_parser = parse_stage_limit)]
+    pub stage_time_limit: Vec<(Stage, u64)>,
+
+    /// Stop a single stage once the program uses this many megabytes of memory and move on to the
+    /// next one.
+    ///
+    /// The stages are `nerve`, `pinch`, `accretion`, `minimize`, and `reduce-pair`. This option can
+    /// be given once for each stage. Memory usage can only be measured on Linux.
+    #[arg(long, value_name = "STAGE=MB", value_parser = parse_stage_limit)]
+    pub stage_memory_limit: Vec<(Stage, u64)>,
+
+    /// Check that the output has the same homology as the input over the field with P elements.
+    ///
+    /// The reduced homology of the input, or of the complex reduced with Čech nerves if the input
+    /// has more than a million faces, is compared with the homology of the output: the relative
+    /// homology of the pair, or the reduced homology of the complex with `--no-pair`. The program
+    /// exits with an error if they differ. With `--acyclic`, P must be the same prime. If a large
+    /// input is interrupted before it is reduced with Čech nerves, the check is skipped.
+    #[arg(
+        long,
+        value_name = "P",
+        num_args = 0..=1,
+        default_missing_value = "2",
+        value_parser = parse_prime
+    )]
+    pub verify: Option<u32>,
+
+    /// Check that every stage preserves the Euler characteristic, and panic otherwise.
+    ///
+    /// Once there is a subcomplex, the Euler characteristic of the pair X, A is χ(X) - χ(A) + 1,
+    /// the Euler characteristic of the quotient X/A. The Euler characteristic is computed from the
+    /// facets without listing every face, but this still takes a while for large complexes.
+    #[arg(long, default_value_t = false)]
+    pub verify_euler: bool,
+
+    /// Simplify each connected component of the input separately and in parallel.
+    ///
+    /// The subcomplex of the output pair only grows within a single component, so disconnected
+    /// inputs usually give much smaller pairs this way. Unless `--component-output` is given, the
+    /// outputs for the components are merged into a single pair with the homotopy type of the
+    /// input. A subcomplex would join the components it meets, so only the first component keeps
+    /// its subcomplex, and each other output pair X, A is printed as X with a cone on A attached,
+    /// whose apex is a new vertex. If the run is cut short, the components which were not
+    /// simplified are printed as they are.
+    ///
+    /// This cannot be combined with per-stage limits, checkpoints, or reports.
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = [
+            "stage_time_limit",
+            "stage_memory_limit",
+            "checkpoint",
+            "resume",
+            "report",
+        ]
+    )]
+    pub components: bool,
+
+    /// With `--components`, write the output for the i-th component to DIR/component-i.sc
+    /// instead of printing the merged output.
+    #[arg(long, value_name = "DIR", requires = "components")]
+    pub component_output: Option<PathBuf>,
+
+    /// If the program is interrupted or a global limit is reached, save its progress to FILE.
+    ///
+    /// The run can then be continued from where it stopped with `--resume FILE`.
+    #[arg(long, value_name = "FILE")]
+    pub checkpoint: Option<PathBuf>,
+
+    /// Continue an interrupted run from a checkpoint saved with `--checkpoint` instead of reading
+    /// a complex from stdin.
+    ///
+    /// Once accretion has begun, the resumed run uses the `--acyclic` setting of the interrupted
+    /// run and must print a pair.
+    #[arg(long, value_name = "FILE")]
+    pub resume: Option<PathBuf>,
+
+    /// Write statistics about the run to FILE as JSON.
+    ///
+    /// For each stage that runs, the report records its wall time, its peak memory usage, the
+    /// numbers of vertices and facets and the dimension of the complex (and subcomplex) it
+    /// produced, the number of nerves taken or edges pinched, and whether it was cut short.
+    #[arg(long, value_name = "FILE")]
+    pub report: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct HomologyArgs {
+    /// Compute homology over the field with P elements, where P is prime.
+    #[arg(short = 'p', long, default_value_t = 2, value_name = "P", value_parser = parse_prime)]
+    pub modulus: u32,
+
+    /// Read a pair X, A of complexes delineated by a blank line, such as the output of `simplify`,
+    /// and print the Betti numbers of the pair.
+    #[arg(long, default_value_t = false)]
+    pub pair: bool,
+
+    /// Print the unreduced Betti numbers of a complex rather than the reduced ones.
+    #[arg(short, long, default_value_t = false, conflicts_with = "pair")]
+    pub unreduced: bool,
+
+    /// Compute the homology of the input as it is.
+    ///
+    /// Homology is computed by enumerating every face, so by default a single complex is first
+    /// simplified with Čech nerves and pinching. Pairs are never simplified.
+    #[arg(short = 'N', long, default_value_t = false)]
+    pub no_simplify: bool,
+
+    /// Print the Betti numbers of the orbits of faces under the permutations in the input.
+    ///
+    /// These are the Betti numbers of the chain complex of coinvariants, whose cells are the orbits
+    /// of faces, so the faces are listed one orbit at a time. When P does not divide the order of
+    /// the group, they are the dimensions of the parts of the homology groups fixed by the group.
+    #[arg(short, long, default_value_t = false, conflicts_with = "pair")]
+    pub invariant: bool,
+
+    /// Also print a representative cycle of each class of a basis, as a chain on the faces of the
+    /// input.
+    ///
+    /// The cycles are found in the simplified complex, and lifted back through each pinched edge
+    /// and each nerve. The `i`-th cycle of the basis in dimension `d` is printed as a line
+    /// `zd.i = [v w] - 2[w x] + ...`, with coefficients between -P/2 and P/2.
+    #[arg(long, default_value_t = false, conflicts_with_all = ["pair", "invariant"])]
+    pub cycles: bool,
+}
+
+#[derive(Args)]
+pub struct CohomologyArgs {
+    /// Compute the cohomology of the input as it is.
+    ///
+    /// Cohomology is computed by enumerating every face, so by default the complex is first
+    /// simplified with Čech nerves and pinching, which preserve the cohomology ring.
+    #[arg(short = 'N', long, default_value_t = false)]
+    pub no_simplify: bool,
+
+    /// Print a representative cocycle of each class of the basis, as a line `xd.i = f + g + ...`
+    /// listing the faces on which it is 1.
+    #[arg(long, default_value_t = false)]
+    pub cocycles: bool,
+}
+
+#[derive(Args)]
+pub struct FundamentalGroupArgs {
+    /// Compute the fundamental group of the input as it is.
+    #[arg(short = 'N', long, default_value_t = false)]
+    pub no_simplify: bool,
+}
+
+#[derive(Args)]
+pub struct PersistenceArgs {
+    /// Compute persistent homology over the field with P elements, where P is prime.
+    #[arg(short = 'p', long, default_value_t = 2, value_name = "P", value_parser = parse_prime)]
+    pub modulus: u32,
+
+    /// Compute the barcode of the filtration as it is.
+    ///
+    /// Every face is listed, so by default pairs of faces which enter at the same time are first
+    /// removed by collapses. Each complex of the filtration either collapses or is left alone, so
+    /// this only discards bars of length zero.
+    #[arg(short = 'N', long, default_value_t = false)]
+    pub no_simplify: bool,
+}
+
+#[derive(Args)]
+pub struct RecognizeArgs {
+    /// Recognize the input as it is, without nerves or pinching.
+    #[arg(short = 'N', long, default_value_t = false)]
+    pub no_simplify: bool,
+
+    /// Read a pair X, A of complexes delineated by a blank line, such as the output of `simplify`,
+    /// and recognize the homotopy type of the pair, i.e. of the mapping cone of the inclusion of A
+    /// into X.
+    ///
+    /// The mapping cone is built by adding a vertex and the cone over A with this apex to X. If A
+    /// is empty, the cone is the apex alone, which gives X with a disjoint basepoint.
+    #[arg(long, default_value_t = false)]
+    pub pair: bool,
+
+    /// Give up if the simplified complex has more than N faces.
+    ///
+    /// Every face is enumerated to compute integral homology, with dense matrices in the worst
+    /// case.
+    #[arg(long, default_value_t = 100_000, value_name = "N")]
+    pub max_faces: u128,
+}
+
+#[derive(Subcommand)]
+pub enum Generator {
+    /// The complex of non-empty partial bijections between sets with A and B elements
+    PartialBijection {
+        a: u8,
+        b: u8,
+        /// Print a single facet and permutations generating S_A × S_B, whose orbit is the complex.
+        #[arg(long, default_value_t = false)]
+        symmetric: bool,
+    },
+    /// The boundary of the (DIM + 1)-simplex
+    Sphere { dim: u32 },
+    /// Möbius' 7-vertex torus
+    Torus,
+    /// The 6-vertex real projective plane
+    ProjectivePlane,
+    /// A 9-vertex Klein bottle
+    KleinBottle,
+    /// Kühnel's 9-vertex complex projective plane
+    ComplexProjectivePlane,
+    /// The lens space L(P, Q), for coprime P > 1 and Q
+    LensSpace { p: u32, q: u32 },
+    /// The Poincaré homology sphere
+    PoincareSphere,
+    /// An 8-vertex dunce hat, which is contractible but not collapsible
+    DunceHat,
+    /// Bing's house with two rooms, which is contractible but not collapsible
+    BingsHouse,
+    /// The matching complex of the complete graph on N vertices
+    Matching { n: u32 },
+    /// The chessboard complex of non-attacking rook placements on an M by N board
+    Chessboard { m: u32, n: u32 },
+    /// The independence complex of the cycle with N vertices
+    CycleIndependence { n: u32 },
+    /// The complex of graphs on N vertices which are not K-connected
+    NotKConnected { n: u32, k: u32 },
+    /// The order complex of the proper part of the Boolean lattice of subsets of an N-element set
+    BooleanLattice { n: u32 },
+    /// The order complex of the proper part of the lattice of partitions of an N-element set
+    PartitionLattice { n: u32 },
+    /// The Linial–Meshulam random complex Y_D(N, P): the full (D - 1)-skeleton of the simplex on N
+    /// vertices, with each D-face present independently with probability P
+    LinialMeshulam {
+        n: u32,
+        d: u32,
+        #[arg(value_parser = parse_probability)]
+        p: f64,
+        /// The seed of the random generator
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// The clique complex of the Erdős–Rényi random graph G(N, P), in which each edge is present
+    /// independently with probability P
+    RandomFlag {
+        n: u32,
+        #[arg(value_parser = parse_probability)]
+        p: f64,
+        /// The seed of the random generator
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// The complex generated by COUNT random faces, each made of SIZE distinct vertices out of N
+    RandomFacets {
+        n: u32,
+        count: usize,
+        size: u32,
+        /// The seed of the random generator
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Strategy {
+    /// Seed from the largest facet and accrete facets in the order in which they are found.
+    Fifo,
+    /// Seed from the facet whose vertices lie in the most facets.
+    Degree,
+    /// Seed from the facet which meets the most other facets.
+    Neighbors,
+    /// Seed from the largest facet and accrete the facets with the largest overlaps first.
+    Overlap,
+    /// Grow subcomplexes from several disjoint facets and merge them.
+    Seeds,
+}
+
+impl SimplifyArgs {
+    // The constraints between options which clap cannot check, since `--xml` is shared by every
+    // subcommand and `--acyclic` and `--verify` must take the same value
+    fn conflict(&self, shared: &SharedArgs) -> Option<(ErrorKind, String)> {
+        if shared.xml && !self.no_pair && self.component_output.is_none() {
+            return Some((
+                ErrorKind::MissingRequiredArgument,
+                "the argument '--xml' requires '--no-pair', since pairs cannot be printed in the \
+                 XML format"
+                    .to_string(),
+            ));
+        }
+        match (self.acyclic, self.verify) {
+            (Some(p), Some(q)) if p != q => Some((
+                ErrorKind::ArgumentConflict,
+                format![
+                    "the argument '--verify {q}' cannot be used with '--acyclic {p}', since an \
+                     acyclic subcomplex only preserves homology over its own field"
+                ],
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn global_limits(&self) -> Limits {
+        Limits {
+            time: self.time_limit.map(Duration::from_secs),
+            memory: self.memory_limit.map(|m| m * 1024),
+        }
+    }
+
+    pub fn stage_limits(&self) -> HashMap<Stage, Limits> {
+        let mut limits = HashMap::<Stage, Limits>::new();
+        for (stage, t) in &self.stage_time_limit {
+            limits.entry(*stage).or_default().time = Some(Duration::from_secs(*t));
+        }
+        for (stage, m) in &self.stage_memory_limit {
+            limits.entry(*stage).or_default().memory = Some(m * 1024);
+        }
+
+        limits
+    }
+
+    pub fn subcomplex_strategy(&self) -> SubcomplexStrategy {
+        match self.strategy {
+            Strategy::Fifo => SubcomplexStrategy::Fifo,
+            Strategy::Degree => SubcomplexStrategy::Degree,
+            Strategy::Neighbors => SubcomplexStrategy::Neighbors,
+            Strategy::Overlap => SubcomplexStrategy::Overlap,
+            Strategy::Seeds => SubcomplexStrategy::Seeds(self.seed_count),
+        }
+    }
+}
+
+fn parse_prime(s: &str) -> Result<u32, String> {
+    let p: u32 = s.parse().map_err(|e| format!["{e}"])?;
+    if is_prime(p) {
+        Ok(p)
+    } else {
+        Err(format!["{p} is not prime"])
+    }
+}
+
+fn parse_probability(s: &str) -> Result<f64, String> {
+    let p: f64 = s.parse().map_err(|e| format!["{e}"])?;
+    if (0.0..=1.0).contains(&p) {
+        Ok(p)
+    } else {
+        Err(format!["{p} is not between 0 and 1"])
+    }
+}
+
+fn parse_stage_limit(s: &str) -> Result<(Stage, u64), String> {
+    let (stage, limit) = s
+        .split_once('=')
+        .ok_or_else(|| format!["expected STAGE=LIMIT, found `{s}`"])?;
+
+    Ok((
+        Stage::from_str(stage.trim(), true)?,
+        limit.trim().parse().map_err(|e| format!["{e}"])?,
+    ))
 }
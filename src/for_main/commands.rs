@@ -0,0 +1,564 @@
+use itertools::Itertools;
+
+use super::cli::{
+    CohomologyArgs, FundamentalGroupArgs, Generator, HomologyArgs, PersistenceArgs, RecognizeArgs,
+    SharedArgs,
+};
+use super::{head_sty, info_sty_str};
+use sc_simplify::homology::{Certificate, Chain, FilteredComplex};
+use sc_simplify::io::{
+    SC, expand_orbits, read_blocks_and_generators, read_distance_matrix, read_edges,
+    read_filtration, read_table, to_sc, write_facets, write_sc, write_symmetric, write_xml,
+};
+use sc_simplify::progress_indicator::{ProgressBars, ProgressObserver, Silent};
+use sc_simplify::{
+    Face, History, SimplicialComplex, SymmetricComplex, Vertex, generators,
+    partial_bijection_complex, partial_bijection_facets, partial_bijection_symmetric,
+};
+
+pub fn write_complex<Point: Vertex>(sc: &SimplicialComplex<Point>, xml: bool) {
+    if xml {
+        write_xml(sc);
+    } else {
+        write_sc(sc);
+    }
+}
+
+// The permutations of the vertices and the complexes in the input. With `--graph`, `--rips`, or
+// `--cech`, the input is a graph or a metric space, and the only complex is the one built from it.
+pub fn read_shared(shared: &SharedArgs) -> (Vec<Vec<u32>>, Vec<Vec<Face<u32>>>) {
+    let path = shared.input.as_deref();
+    let sc = if shared.graph {
+        SimplicialComplex::<u32>::clique_complex(read_edges(path))
+    } else if let Some((n, distance)) = read_metric(shared) {
+        metric_complex(shared, n, distance)
+    } else {
+        return read_blocks_and_generators(path);
+    };
+
+    (
+        Vec::new(),
+        vec![sc.into_iter().filter(|f| !f.is_empty()).collect()],
+    )
+}
+
+// The distance function on a metric space
+type Distance = Box<dyn Fn(usize, usize) -> f64 + Sync>;
+
+// With `--rips` or `--cech`, the number of points of the input and the distances between them,
+// which are Euclidean unless a distance matrix is given
+fn read_metric(shared: &SharedArgs) -> Option<(usize, Distance)> {
+    let path = shared.input.as_deref();
+    if shared.distance_matrix {
+        let matrix = read_distance_matrix(path);
+        Some((matrix.len(), Box::new(move |i, j| matrix[i][j])))
+    } else if shared.rips.is_some() || shared.cech.is_some() {
+        let points = read_table(path);
+        assert![
+            points.iter().map(|p| p.len()).all_equal(),
+            "The points should all have the same number of coordinates."
+        ];
+        Some((
+            points.len(),
+            Box::new(move |i, j| {
+                points[i]
+                    .iter()
+                    .zip(&points[j])
+                    .map(|(x, y)| (x - y) * (x - y))
+                    .sum::<f64>()
+                    .sqrt()
+            }),
+        ))
+    } else {
+        None
+    }
+}
+
+fn metric_complex<F>(shared: &SharedArgs, n: usize, distance: F) -> SimplicialComplex<u32>
+where
+    F: Fn(usize, usize) -> f64 + Sync,
+{
+    match (shared.rips, shared.cech) {
+        (Some(scale), _) => SimplicialComplex::vietoris_rips(n, distance, scale),
+        (_, Some(scale)) => SimplicialComplex::cech(n, distance, scale),
+        _ => unreachable!["`--distance-matrix` requires `--rips` or `--cech`."],
+    }
+}
+
+fn read_checked(shared: &SharedArgs) -> SC {
+    let (generators, blocks) = read_shared(shared);
+    let facets = expand_orbits(&generators, blocks.into_iter().flatten().collect());
+
+    checked(to_sc(facets), shared)
+}
+
+fn checked(mut sc: SC, shared: &SharedArgs) -> SC {
+    if shared.check_input {
+        match &mut sc {
+            SC::Small(sc) => sc.maximalify(),
+            SC::Large(sc) => sc.maximalify(),
+        }
+    }
+
+    sc
+}
+
+fn print_info<Point: Vertex>(sc: &SimplicialComplex<Point>) {
+    let f_vector = sc.f_vector();
+    println!["vertices {}", f_vector.first().copied().unwrap_or(0)];
+    // The empty complex has a single, empty facet.
+    println!["facets {}", sc.iter().filter(|f| !f.is_empty()).count()];
+    println!["dimension {}", sc.dimension()];
+    println!["pure {}", sc.is_pure()];
+    println!["components {}", sc.component_count()];
+    println!["euler-characteristic {}", sc.euler_characteristic()];
+    println!["f-vector {}", f_vector.iter().join(" ")];
+    // The number of vertices of each degree in the 1-skeleton, as `degree:count` pairs
+    println![
+        "degrees {}",
+        sc.degree_distribution()
+            .iter()
+            .map(|(d, n)| format!["{d}:{n}"])
+            .join(" ")
+    ];
+}
+
+// The invariants of `print_info` which can be computed one orbit at a time
+fn print_symmetric_info(sc: &SymmetricComplex) {
+    let f_vector = sc.f_vector();
+    println!["vertices {}", f_vector.first().copied().unwrap_or(0)];
+    // The empty complex has a single, empty facet.
+    let facet_count = if sc.is_empty() { 0 } else { sc.facet_count() };
+    println!["facets {facet_count}"];
+    println!["facet-orbits {}", sc.representatives().len()];
+    println!["dimension {}", sc.dimension()];
+    println!["pure {}", sc.is_pure()];
+    println!["euler-characteristic {}", sc.euler_characteristic()];
+    println!["f-vector {}", f_vector.iter().join(" ")];
+}
+
+pub fn info(shared: &SharedArgs) {
+    let (generators, blocks) = read_shared(shared);
+    let facets = blocks.into_iter().flatten();
+    if !generators.is_empty() {
+        print_symmetric_info(&SymmetricComplex::new(generators, facets));
+        return;
+    }
+
+    match checked(to_sc(facets.collect()), shared) {
+        SC::Small(sc) => print_info(&sc),
+        SC::Large(sc) => print_info(&sc),
+    }
+}
+
+pub fn convert(shared: &SharedArgs) {
+    match read_checked(shared) {
+        SC::Small(sc) => write_complex(&sc, shared.xml),
+        SC::Large(sc) => write_complex(&sc, shared.xml),
+    }
+}
+
+fn to_complex(facets: Vec<Face<u32>>, check_input: bool) -> SimplicialComplex<u32> {
+    if facets.is_empty() {
+        SimplicialComplex::default()
+    } else if check_input {
+        SimplicialComplex::from_check_maximal(facets)
+    } else {
+        SimplicialComplex::from_check_sorted(facets)
+    }
+}
+
+fn progress_for(shared: &SharedArgs) -> Box<dyn ProgressObserver> {
+    if shared.quiet {
+        Box::new(Silent)
+    } else {
+        eprintln!["{}", head_sty("Simplifying before computing homology:")];
+        Box::new(ProgressBars::new())
+    }
+}
+
+// The complex in the input, simplified with Čech nerves and pinching unless `no_simplify` is set.
+// Both preserve the homotopy type, so the (co)homology is computed from the result. The
+// simplifications are recorded in `history` if given.
+fn simplified(
+    generators: Vec<Vec<u32>>,
+    blocks: Vec<Vec<Face<u32>>>,
+    no_simplify: bool,
+    history: Option<&mut History<u32>>,
+    shared: &SharedArgs,
+) -> SimplicialComplex<u32> {
+    let facets = blocks.into_iter().flatten().collect();
+    let progress = (!no_simplify).then(|| progress_for(shared));
+    let facets = match &progress {
+        // A complex given by orbits of facets is reduced with nerves before they are listed,
+        // unless the nerves are recorded.
+        Some(progress) if !generators.is_empty() && history.is_none() => {
+            let mut sc = SymmetricComplex::new(generators, facets);
+            sc.nerve_reduce(None, &**progress);
+            sc.facets().filter(|f| !f.is_empty()).collect()
+        }
+        _ => expand_orbits(&generators, facets),
+    };
+    let mut sc = to_complex(facets, shared.check_input);
+    match (progress, history) {
+        (Some(progress), Some(history)) => {
+            sc.nerve_reduce_recorded(history, None, &*progress);
+            while sc.pinch_recorded(history, None, &*progress) > 0 {}
+        }
+        (Some(progress), None) => {
+            sc.nerve_reduce(None, &*progress);
+            while sc.pinch(None, &*progress) > 0 {}
+        }
+        _ => {}
+    }
+
+    sc
+}
+
+pub fn homology(args: &HomologyArgs, shared: &SharedArgs) {
+    let (generators, blocks) = read_shared(shared);
+
+    let betti = if args.invariant {
+        // Pinching edges would not respect the group action.
+        let mut sc = SymmetricComplex::new(generators, blocks.into_iter().flatten());
+        if !args.no_simplify {
+            sc.nerve_reduce(None, &*progress_for(shared));
+            if !shared.quiet {
+                eprintln!["\n{}", info_sty_str("Computing homology")];
+            }
+        }
+        if args.unreduced {
+            sc.invariant_betti_numbers(args.modulus)
+        } else {
+            sc.reduced_invariant_betti_numbers(args.modulus)
+        }
+    } else if args.pair {
+        let mut blocks = blocks.into_iter().map(|b| expand_orbits(&generators, b));
+        let first = blocks.next().unwrap_or_default();
+        let sub = to_complex(blocks.flatten().collect(), shared.check_input);
+        // The complex of a pair printed by `simplify` need not contain its subcomplex.
+        let sc =
+            SimplicialComplex::from_check_maximal(first.into_iter().chain(sub.iter().cloned()));
+        sc.relative_betti_numbers(&sub, args.modulus)
+    } else if args.cycles {
+        let mut history = History::default();
+        let sc = simplified(
+            generators,
+            blocks,
+            args.no_simplify,
+            Some(&mut history),
+            shared,
+        );
+        if !args.no_simplify && !shared.quiet {
+            eprintln!["\n{}", info_sty_str("Computing homology")];
+        }
+        let cycles = if args.unreduced {
+            sc.cycles(args.modulus)
+        } else {
+            sc.reduced_cycles(args.modulus)
+        };
+        println!["{}", cycles.iter().map(|c| c.len()).join(" ")];
+        // The `i`-th cycle of the basis in dimension `d` is called `zd.i`.
+        for (d, cycles) in cycles.iter().enumerate() {
+            for (i, cycle) in cycles.iter().enumerate() {
+                let lifted = history.lift(cycle, args.modulus);
+                println!["z{d}.{i} = {}", format_chain(&lifted, args.modulus)];
+            }
+        }
+        return;
+    } else {
+        let sc = simplified(generators, blocks, args.no_simplify, None, shared);
+        if !args.no_simplify && !shared.quiet {
+            eprintln!["\n{}", info_sty_str("Computing homology")];
+        }
+        if args.unreduced {
+            sc.betti_numbers(args.modulus)
+        } else {
+            sc.reduced_betti_numbers(args.modulus)
+        }
+    };
+
+    println!["{}", betti.iter().join(" ")];
+}
+
+// A chain such as `[0 1] + [1 2] - [0 2]`, where the coefficients are taken between -p/2 and p/2
+fn format_chain(chain: &Chain<u32>, modulus: u32) -> String {
+    chain
+        .iter()
+        .enumerate()
+        .map(|(i, (face, c))| {
+            let (sign, c) = if *c > modulus / 2 {
+                ("-", modulus - c)
+            } else {
+                ("+", *c)
+            };
+            let coefficient = if c == 1 { String::new() } else { c.to_string() };
+            let sign = match (i, sign) {
+                (0, "+") => "",
+                (0, _) => "-",
+                _ => &format![" {sign} "],
+            };
+            format!["{sign}{coefficient}[{}]", face.iter().join(" ")]
+        })
+        .join("")
+}
+
+pub fn cohomology(args: &CohomologyArgs, shared: &SharedArgs) {
+    let (generators, blocks) = read_shared(shared);
+    let sc = simplified(generators, blocks, args.no_simplify, None, shared);
+    if !args.no_simplify && !shared.quiet {
+        eprintln!["\n{}", info_sty_str("Computing cohomology")];
+    }
+    let cohomology = sc.cohomology();
+    let betti = cohomology.betti_numbers();
+    println!["{}", betti.iter().join(" ")];
+
+    // The `i`-th class of the basis in degree `d` is called `x{d}.{i}`.
+    let classes = betti
+        .iter()
+        .enumerate()
+        .flat_map(|(d, b)| (0..*b).map(move |i| (d, i)))
+        .collect_vec();
+    let name = |(d, i): (usize, usize)| format!["x{d}.{i}"];
+    if args.cocycles {
+        for &(d, i) in &classes {
+            println![
+                "{} = {}",
+                name((d, i)),
+                cohomology
+                    .cocycle(d, i)
+                    .iter()
+                    .map(|f| f.iter().join(" "))
+                    .join(" + ")
+            ];
+        }
+    }
+    // The classes of degree 0 are the indicators of the components, so only the products of
+    // classes of positive degree are printed. Over F_2 the cup product is commutative.
+    for (a, b) in classes
+        .iter()
+        .filter(|(d, _)| *d > 0)
+        .tuple_combinations()
+        .chain(classes.iter().filter(|(d, _)| *d > 0).map(|a| (a, a)))
+        .filter(|(a, b)| a.0 + b.0 < betti.len())
+        .sorted()
+    {
+        let product = cohomology.cup(*a, *b);
+        if !product.is_empty() {
+            println![
+                "{} * {} = {}",
+                name(*a),
+                name(*b),
+                product.iter().map(|i| name((a.0 + b.0, *i))).join(" + ")
+            ];
+        }
+    }
+}
+
+// A word such as `x1*x2^-1`, or `1` for the empty word
+pub fn format_word(word: &[i32]) -> String {
+    if word.is_empty() {
+        return "1".to_string();
+    }
+
+    word.iter()
+        .map(|a| {
+            if *a > 0 {
+                format!["x{a}"]
+            } else {
+                format!["x{}^-1", -a]
+            }
+        })
+        .join("*")
+}
+
+pub fn fundamental_group(args: &FundamentalGroupArgs, shared: &SharedArgs) {
+    let (generators, blocks) = read_shared(shared);
+    let sc = simplified(generators, blocks, args.no_simplify, None, shared);
+    if !args.no_simplify && !shared.quiet {
+        eprintln!["\n{}", info_sty_str("Computing the fundamental group")];
+    }
+    let presentation = sc.fundamental_group();
+
+    println![
+        "generators{}",
+        (1..=presentation.generator_count)
+            .map(|k| format![" x{k}"])
+            .join("")
+    ];
+    for relation in &presentation.relations {
+        println!["relation {}", format_word(relation)];
+    }
+}
+
+pub fn recognize(args: &RecognizeArgs, shared: &SharedArgs) {
+    let (generators, blocks) = read_shared(shared);
+    let (generators, blocks) = if args.pair {
+        // The mapping cone of the inclusion of the subcomplex, whose apex is a new vertex. The
+        // cone on the empty subcomplex is the apex alone, so an empty subcomplex gives the
+        // complex with a disjoint basepoint.
+        let mut blocks = blocks.into_iter().map(|b| expand_orbits(&generators, b));
+        let first = blocks.next().unwrap_or_default();
+        let mut sub = blocks.flatten().collect_vec();
+        if sub.is_empty() {
+            sub.push(Face::default());
+        }
+        let apex = first
+            .iter()
+            .chain(&sub)
+            .flatten()
+            .max()
+            .map_or(0, |v| v + 1);
+        let cone = sub
+            .into_iter()
+            .map(|f| Face::from_iter(f.iter().copied().chain([apex])));
+        (Vec::new(), vec![first.into_iter().chain(cone).collect()])
+    } else {
+        (generators, blocks)
+    };
+    let sc = simplified(generators, blocks, args.no_simplify, None, shared);
+
+    let faces: u128 = sc.f_vector().iter().sum();
+    if faces > args.max_faces {
+        if !shared.quiet {
+            eprintln![
+                "\n{}",
+                info_sty_str(format![
+                    "The simplified complex has {faces} faces, more than {}",
+                    args.max_faces
+                ])
+            ];
+        }
+        println!["unknown"];
+        return;
+    }
+    if !args.no_simplify && !shared.quiet {
+        eprintln!["\n{}", info_sty_str("Recognizing the homotopy type")];
+    }
+
+    let Some(wedge) = sc.wedge_of_spheres() else {
+        println!["unknown"];
+        return;
+    };
+    println!["wedge {} {}", wedge.count, wedge.dimension];
+    let certificate = match wedge.certificate {
+        Certificate::Collapsible => "collapses to a vertex".to_string(),
+        Certificate::Graph { vertices, edges } => {
+            format!["collapses to a connected graph with {vertices} vertices and {edges} edges"]
+        }
+        Certificate::SimplexBoundary => format![
+            "collapses to the boundary of the {}-simplex",
+            wedge.dimension + 1
+        ],
+        Certificate::SimplyConnected if wedge.count == 0 => {
+            "simply connected and acyclic over the integers".to_string()
+        }
+        Certificate::SimplyConnected => format![
+            "simply connected with integral homology Z^{} in dimension {} only",
+            wedge.count, wedge.dimension
+        ],
+    };
+    println!["certificate {certificate}"];
+}
+
+pub fn persistence(args: &PersistenceArgs, shared: &SharedArgs) {
+    assert![
+        !shared.graph,
+        "A filtration cannot be read from a graph without weights."
+    ];
+    let mut filtration = match read_metric(shared) {
+        Some((n, distance)) => {
+            let sc = metric_complex(shared, n, &distance);
+            // A face appears in the Vietoris–Rips complex at the greatest distance between its
+            // vertices, and in the Čech complex at the least radius of a ball around a point of
+            // the input containing it.
+            let diameter = |f: &[u32]| {
+                f.iter()
+                    .tuple_combinations()
+                    .map(|(v, w)| distance(*v as usize, *w as usize))
+                    .fold(0.0, f64::max)
+            };
+            let radius = |f: &[u32]| {
+                (0..n)
+                    .map(|c| {
+                        f.iter()
+                            .map(|v| distance(c, *v as usize))
+                            .fold(0.0, f64::max)
+                    })
+                    .fold(f64::INFINITY, f64::min)
+            };
+            if shared.rips.is_some() {
+                FilteredComplex::from_times(&sc, diameter)
+            } else {
+                FilteredComplex::from_times(&sc, radius)
+            }
+        }
+        None => FilteredComplex::from_facets(read_filtration(shared.input.as_deref())),
+    };
+
+    if !args.no_simplify {
+        let pairs = filtration.collapse();
+        if !shared.quiet {
+            eprintln![
+                "{}",
+                info_sty_str(format![
+                    "Removed {pairs} pairs of faces by collapses, leaving {} faces",
+                    filtration.len()
+                ])
+            ];
+        }
+    }
+    for bar in filtration.barcode(args.modulus) {
+        println!["{} {} {}", bar.dimension, bar.birth, bar.death];
+    }
+}
+
+pub fn generate(generator: &Generator, shared: &SharedArgs) {
+    let sc = match *generator {
+        Generator::PartialBijection {
+            a,
+            b,
+            symmetric: true,
+        } => {
+            write_symmetric(&partial_bijection_symmetric(a, b));
+            return;
+        }
+        // The facets are written as they are generated, so that large complexes fit in memory.
+        Generator::PartialBijection { a, b, .. } if !shared.xml => {
+            write_facets(partial_bijection_facets(a, b));
+            return;
+        }
+        Generator::PartialBijection { a, b, .. } => partial_bijection_complex(a, b),
+        Generator::Sphere { dim } => generators::sphere(dim),
+        Generator::Torus => generators::torus(),
+        Generator::ProjectivePlane => generators::projective_plane(),
+        Generator::KleinBottle => generators::klein_bottle(),
+        Generator::ComplexProjectivePlane => generators::complex_projective_plane(),
+        Generator::LensSpace { p, q } => generators::lens_space(p, q),
+        Generator::PoincareSphere => generators::poincare_sphere(),
+        Generator::DunceHat => generators::dunce_hat(),
+        Generator::BingsHouse => generators::bings_house(),
+        Generator::Matching { n } => {
+            generators::matching_complex(&(0..n).tuple_combinations().collect_vec())
+        }
+        Generator::Chessboard { m, n } => generators::chessboard_complex(m, n),
+        Generator::CycleIndependence { n } => {
+            generators::independence_complex(n, &(0..n).map(|v| (v, (v + 1) % n)).collect_vec())
+        }
+        Generator::NotKConnected { n, k } => generators::not_k_connected_complex(n, k),
+        Generator::BooleanLattice { n } => generators::boolean_lattice_complex(n),
+        Generator::PartitionLattice { n } => generators::partition_lattice_complex(n),
+        Generator::LinialMeshulam { n, d, p, seed } => {
+            generators::linial_meshulam_complex(n, d, p, seed)
+        }
+        Generator::RandomFlag { n, p, seed } => generators::random_flag_complex(n, p, seed),
+        Generator::RandomFacets {
+            n,
+            count,
+            size,
+            seed,
+        } => generators::random_facet_complex(n, count, size, seed),
+    };
+
+    write_complex(&sc, shared.xml);
+}
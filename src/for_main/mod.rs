@@ -1,8 +1,21 @@
 use console::{Style, StyledObject};
 use std::fmt::Display;
 
+mod budget;
+mod checkpoint;
 mod cli;
-pub use cli::Cli;
+mod commands;
+mod report;
+mod verify;
+pub use budget::{Budget, Cut, Stage};
+pub use checkpoint::{AnyCheckpoint, Checkpoint, read_checkpoint};
+pub use cli::{Cli, Command, SharedArgs, SimplifyArgs, usage_error};
+pub use commands::{
+    cohomology, convert, fundamental_group, generate, homology, info, persistence, read_shared,
+    recognize, write_complex,
+};
+pub use report::Report;
+pub use verify::{Verification, state_euler_characteristic};
 
 pub fn info_sty_str<S: Display>(text: S) -> StyledObject<String> {
     Style::new()
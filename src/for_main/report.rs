@@ -0,0 +1,140 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use super::budget::{Cut, Stage, peak_memory, reset_peak_memory};
+use sc_simplify::{SimplicialComplex, Vertex};
+
+#[derive(Serialize)]
+struct ComplexStats {
+    vertices: usize,
+    facets: usize,
+    // The empty complex has dimension -1.
+    dimension: i64,
+}
+
+impl ComplexStats {
+    fn of<Point: Vertex>(sc: &SimplicialComplex<Point>) -> Self {
+        // Every facet is counted except the empty face of the empty complex. The facets may not be
+        // sorted, e.g. after accretion.
+        Self {
+            vertices: sc.iter().flatten().collect::<HashSet<_>>().len(),
+            facets: sc.iter().filter(|f| !f.is_empty()).count(),
+            dimension: sc.iter().map(|f| f.len()).max().unwrap_or(0) as i64 - 1,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct StageReport {
+    stage: Stage,
+    wall_time_secs: f64,
+    peak_memory_kib: Option<u64>,
+    // The state of the complexes when the stage ended, as in a checkpoint
+    complex: ComplexStats,
+    subcomplex: Option<ComplexStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nerve_iterations: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pinched_edges: Option<usize>,
+    interrupted: bool,
+    cut_short_because: Option<String>,
+}
+
+// Statistics about a run, written as JSON with `--report`. Nothing is recorded if the report is
+// disabled, since counting the vertices of a large complex takes a while.
+#[derive(Serialize)]
+pub struct Report {
+    #[serde(skip)]
+    enabled: bool,
+    #[serde(skip)]
+    start: Instant,
+    #[serde(skip)]
+    stage_start: Instant,
+    input: Option<ComplexStats>,
+    stages: Vec<StageReport>,
+    wall_time_secs: f64,
+    peak_memory_kib: Option<u64>,
+    interrupted: bool,
+}
+
+impl Report {
+    pub fn new<Point: Vertex>(enabled: bool, sc: &SimplicialComplex<Point>) -> Self {
+        Self {
+            enabled,
+            start: Instant::now(),
+            stage_start: Instant::now(),
+            input: enabled.then(|| ComplexStats::of(sc)),
+            stages: Vec::new(),
+            wall_time_secs: 0.0,
+            peak_memory_kib: None,
+            interrupted: false,
+        }
+    }
+
+    pub fn begin(&mut self) {
+        if self.enabled {
+            self.record_peak(peak_memory());
+            reset_peak_memory();
+            self.stage_start = Instant::now();
+        }
+    }
+
+    pub fn end<Point: Vertex>(
+        &mut self,
+        stage: Stage,
+        cut: Option<Cut>,
+        sc: &SimplicialComplex<Point>,
+        sub: Option<&SimplicialComplex<Point>>,
+    ) {
+        if self.enabled {
+            let peak = peak_memory();
+            self.record_peak(peak);
+            self.stages.push(StageReport {
+                stage,
+                wall_time_secs: self.stage_start.elapsed().as_secs_f64(),
+                peak_memory_kib: peak,
+                complex: ComplexStats::of(sc),
+                subcomplex: sub.map(ComplexStats::of),
+                nerve_iterations: None,
+                pinched_edges: None,
+                interrupted: cut.is_some(),
+                cut_short_because: cut.map(|c| c.explanation()),
+            });
+        }
+    }
+
+    // Record the number of nerves taken in the last stage.
+    pub fn nerve_iterations(&mut self, n: usize) {
+        if let Some(stage) = self.stages.last_mut() {
+            stage.nerve_iterations = Some(n);
+        }
+    }
+
+    // Record the number of edges pinched in the last stage.
+    pub fn pinched_edges(&mut self, n: usize) {
+        if let Some(stage) = self.stages.last_mut() {
+            stage.pinched_edges = Some(n);
+        }
+    }
+
+    fn record_peak(&mut self, peak: Option<u64>) {
+        self.peak_memory_kib = self.peak_memory_kib.max(peak);
+    }
+
+    pub fn write(&mut self, path: &Path, interrupted: bool) -> io::Result<()> {
+        self.wall_time_secs = self.start.elapsed().as_secs_f64();
+        self.record_peak(peak_memory());
+        self.interrupted = interrupted;
+
+        let mut out = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut out, self)?;
+        writeln![out]?;
+
+        out.flush()
+    }
+}
@@ -0,0 +1,121 @@
+use super::budget::Stage;
+use sc_simplify::{SimplicialComplex, Vertex};
+
+// The reference homology for `--verify` is computed from the input only if it has at most this
+// many faces, and from the complex reduced with Čech nerves otherwise.
+const MAX_REFERENCE_FACES: u128 = 1_000_000;
+
+// The Euler characteristic of the space which a state of a run represents: the complex, or the
+// quotient of the union of the complex and the subcomplex by the subcomplex, which is acyclic
+pub fn state_euler_characteristic<Point: Vertex>(
+    sc: &SimplicialComplex<Point>,
+    sub: Option<&SimplicialComplex<Point>>,
+) -> i128 {
+    match sub {
+        None => sc.euler_characteristic(),
+        Some(sub) => {
+            SimplicialComplex::from_iter(sc.iter().chain(sub).cloned()).euler_characteristic()
+                - sub.euler_characteristic()
+                + 1
+        }
+    }
+}
+
+// The reduced homology over F_p of the space which a state of a run represents, without trailing
+// zeros
+fn state_homology<Point: Vertex>(
+    sc: &SimplicialComplex<Point>,
+    sub: Option<&SimplicialComplex<Point>>,
+    modulus: u32,
+) -> Vec<usize> {
+    let mut betti = match sub {
+        None => sc.reduced_betti_numbers(modulus),
+        Some(sub) => SimplicialComplex::from_check_maximal(sc.iter().chain(sub).cloned())
+            .relative_betti_numbers(sub, modulus),
+    };
+    while betti.last() == Some(&0) {
+        betti.pop();
+    }
+
+    betti
+}
+
+// Compares the homology of the output of a run with the homology of its input, or of an
+// intermediate complex if the input is too large
+pub struct Verification {
+    modulus: Option<u32>,
+    // The homology of the reference and the stage after which it was taken, if any
+    reference: Option<(Option<Stage>, Vec<usize>)>,
+}
+
+impl Verification {
+    pub fn new(modulus: Option<u32>) -> Self {
+        Self {
+            modulus,
+            reference: None,
+        }
+    }
+
+    // Whether a reference is still needed and the homology of `sc` is cheap enough to compute
+    pub fn wants<Point: Vertex>(&self, sc: &SimplicialComplex<Point>) -> bool {
+        self.modulus.is_some()
+            && self.reference.is_none()
+            && sc.f_vector().iter().sum::<u128>() <= MAX_REFERENCE_FACES
+    }
+
+    // Take the homology of a state as the reference unless there is one already.
+    pub fn record<Point: Vertex>(
+        &mut self,
+        after: Option<Stage>,
+        sc: &SimplicialComplex<Point>,
+        sub: Option<&SimplicialComplex<Point>>,
+    ) {
+        if let Some(p) = self.modulus
+            && self.reference.is_none()
+        {
+            self.reference = Some((after, state_homology(sc, sub, p)));
+        }
+    }
+
+    // Compare the homology of the output with the reference. Return a description of the outcome,
+    // and whether the homology agrees, unless verification is disabled.
+    pub fn check<Point: Vertex>(
+        &self,
+        sc: &SimplicialComplex<Point>,
+        sub: Option<&SimplicialComplex<Point>>,
+    ) -> Option<(bool, String)> {
+        let p = self.modulus?;
+        // There is no reference if the run was interrupted before the nerve stage and the input
+        // was too large to take as the reference.
+        let Some((after, expected)) = &self.reference else {
+            return Some((
+                true,
+                format![
+                    "The homology over F_{p} was not verified, since the run was interrupted \
+                     before the input was reduced to a reference small enough to compute."
+                ],
+            ));
+        };
+        let found = state_homology(sc, sub, p);
+        let source = match after {
+            None => "the input".to_string(),
+            Some(stage) => format!["the input after {}", stage.description().to_lowercase()],
+        };
+
+        Some(if found == *expected {
+            (
+                true,
+                format![
+                    "The reduced homology over F_{p} of the output agrees with that of {source}: {found:?}."
+                ],
+            )
+        } else {
+            (
+                false,
+                format![
+                    "The reduced homology over F_{p} of the output is {found:?}, but that of {source} is {expected:?}."
+                ],
+            )
+        })
+    }
+}
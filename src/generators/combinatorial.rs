@@ -0,0 +1,157 @@
+// Complexes from combinatorics, many of whose homotopy types are known. Graphs are given by their
+// edges, and vertices of complexes built from edges are numbered by the positions of the edges.
+use itertools::Itertools;
+
+use super::{Labels, SC, complex, subdivision};
+use crate::helpers::SCHashSet;
+use crate::simplicial_complex::maximal_cliques;
+
+// The matching complex of a graph, whose faces are the sets of pairwise disjoint edges. These are
+// the independent sets of the line graph, i.e. the cliques of its complement.
+pub fn matching_complex(edges: &[(u32, u32)]) -> SC {
+    let disjoint: Vec<SCHashSet<u32>> = edges
+        .iter()
+        .map(|(a, b)| {
+            (0..edges.len() as u32)
+                .filter(|i| {
+                    let (c, d) = edges[*i as usize];
+                    ![c, d].contains(a) && ![c, d].contains(b)
+                })
+                .collect()
+        })
+        .collect();
+
+    complex(maximal_cliques(&disjoint))
+}
+
+// The chessboard complex M_{m,n}, whose faces are the placements of non-attacking rooks on an m by
+// n board, i.e. the matching complex of the complete bipartite graph K_{m,n}. The square in row i
+// and column j is the vertex i * n + j.
+pub fn chessboard_complex(m: u32, n: u32) -> SC {
+    // Each facet fills every row or every column.
+    if m <= n {
+        complex(
+            (0..n)
+                .permutations(m as usize)
+                .map(|columns| (0..m).zip(columns).map(|(i, j)| i * n + j).collect_vec()),
+        )
+    } else {
+        complex((0..m).permutations(n as usize).map(|rows| {
+            rows.into_iter()
+                .zip(0..n)
+                .map(|(i, j)| i * n + j)
+                .collect_vec()
+        }))
+    }
+}
+
+// The independence complex of the graph on 0..n with the given edges, whose faces are the sets of
+// pairwise non-adjacent vertices, i.e. the cliques of the complementary graph
+pub fn independence_complex(n: u32, edges: &[(u32, u32)]) -> SC {
+    let adjacent: SCHashSet<(u32, u32)> = edges
+        .iter()
+        .flat_map(|(a, b)| [(*a, *b), (*b, *a)])
+        .collect();
+    let complement: Vec<SCHashSet<u32>> = (0..n)
+        .map(|v| {
+            (0..n)
+                .filter(|w| *w != v && !adjacent.contains(&(v, *w)))
+                .collect()
+        })
+        .collect();
+
+    complex(maximal_cliques(&complement))
+}
+
+// The complex of graphs on n vertices which are not k-connected, whose vertices are the edges of
+// the complete graph in lexicographic order. A graph with more than k vertices is not k-connected
+// when removing some k - 1 vertices disconnects it, so the facets are the graphs with every edge
+// except those between A and B, for the partitions of the vertices into S, A and B with |S| = k - 1
+// and A, B non-empty. Every graph with at most k vertices is not k-connected.
+pub fn not_k_connected_complex(n: u32, k: u32) -> SC {
+    assert![k > 0, "The connectivity of graphs should be positive."];
+    let edges = (0..n).tuple_combinations::<(u32, u32)>().collect_vec();
+    if n <= k {
+        return complex([(0..edges.len() as u32).collect_vec()]);
+    }
+
+    let mut facets = Vec::<Vec<u32>>::new();
+    for separator in (0..n).combinations(k as usize - 1) {
+        let rest = (0..n).filter(|v| !separator.contains(v)).collect_vec();
+        // The first remaining vertex is in A, and the others are in B when their bit is set. The
+        // side of a vertex is 0 in S, 1 in A and 2 in B.
+        for mask in 1..1u64 << (rest.len() - 1) {
+            let mut side = vec![0; n as usize];
+            for (i, v) in rest.iter().enumerate() {
+                side[*v as usize] = if i > 0 && mask >> (i - 1) & 1 == 1 {
+                    2
+                } else {
+                    1
+                };
+            }
+            facets.push(
+                (0..edges.len() as u32)
+                    .filter(|e| {
+                        let (v, w) = edges[*e as usize];
+                        side[v as usize] * side[w as usize] != 2
+                    })
+                    .collect(),
+            );
+        }
+    }
+
+    complex(facets)
+}
+
+// The order complex of the proper part of the Boolean lattice of subsets of an n-element set,
+// which is the barycentric subdivision of the boundary of the (n - 1)-simplex
+pub fn boolean_lattice_complex(n: u32) -> SC {
+    let boundary = (0..n)
+        .map(|v| (0..n).filter(|w| *w != v).collect_vec())
+        .filter(|f| !f.is_empty())
+        .collect_vec();
+    let mut subsets = Labels::new();
+
+    complex(subdivision(&boundary, |face| subsets.label(face.to_vec())))
+}
+
+// The order complex of the proper part of the lattice of partitions of an n-element set. Its
+// maximal chains are obtained by merging two blocks at a time, from the partition into singletons.
+pub fn partition_lattice_complex(n: u32) -> SC {
+    let mut partitions = Labels::new();
+    let mut facets = Vec::<Vec<u32>>::new();
+    merge_chains(
+        (0..n).map(|v| vec![v]).collect(),
+        &mut Vec::new(),
+        &mut partitions,
+        &mut facets,
+    );
+
+    complex(facets)
+}
+
+// Add to `facets` the chains extending `chain`, whose last element is `partition`, until only two
+// blocks remain
+fn merge_chains(
+    partition: Vec<Vec<u32>>,
+    chain: &mut Vec<u32>,
+    partitions: &mut Labels<Vec<Vec<u32>>>,
+    facets: &mut Vec<Vec<u32>>,
+) {
+    if partition.len() <= 2 {
+        facets.push(chain.clone());
+        return;
+    }
+
+    for (i, j) in (0..partition.len()).tuple_combinations() {
+        let mut merged = partition.clone();
+        let block = merged.remove(j);
+        merged[i].extend(block);
+        merged[i].sort_unstable();
+        merged.sort_unstable();
+
+        chain.push(partitions.label(merged.clone()));
+        merge_chains(merged, chain, partitions, facets);
+        chain.pop();
+    }
+}
@@ -0,0 +1,412 @@
+// Standard triangulations of spaces, as examples and as test cases for the simplifications. The
+// vertices of each complex are numbered from 0.
+use std::hash::Hash;
+use std::ops::{Add, Mul, Neg};
+
+use itertools::Itertools;
+use num::Integer;
+
+use crate::helpers::{SCHashMap, new_hm};
+use crate::{Face, SimplicialComplex};
+
+mod combinatorial;
+mod random;
+
+pub use combinatorial::{
+    boolean_lattice_complex, chessboard_complex, independence_complex, matching_complex,
+    not_k_connected_complex, partition_lattice_complex,
+};
+pub use random::{linial_meshulam_complex, random_facet_complex, random_flag_complex};
+
+type SC = SimplicialComplex<u32>;
+
+fn complex<F: IntoIterator<Item = u32>>(facets: impl IntoIterator<Item = F>) -> SC {
+    SC::from_check_unique(facets.into_iter().map(Face::from_iter))
+}
+
+// The boundary of the (dim + 1)-simplex
+pub fn sphere(dim: u32) -> SC {
+    let n = dim + 2;
+
+    complex((0..n).map(|v| (0..n).filter(move |w| *w != v)))
+}
+
+// Möbius' 7-vertex torus
+pub fn torus() -> SC {
+    complex((0..7).flat_map(|i| [[i, (i + 1) % 7, (i + 3) % 7], [i, (i + 2) % 7, (i + 3) % 7]]))
+}
+
+// The 6-vertex real projective plane, the quotient of the icosahedron by the antipodal map
+pub fn projective_plane() -> SC {
+    complex([
+        [0, 1, 2],
+        [0, 2, 3],
+        [0, 3, 4],
+        [0, 4, 5],
+        [0, 1, 5],
+        [1, 2, 4],
+        [2, 3, 5],
+        [1, 3, 4],
+        [2, 4, 5],
+        [1, 3, 5],
+    ])
+}
+
+// A 9-vertex Klein bottle: a 3 by 3 grid of squares, each split into two triangles, whose left and
+// right sides are glued directly and whose top and bottom are glued with a flip
+pub fn klein_bottle() -> SC {
+    let vertex = |i: u32, j: u32| {
+        if j < 3 {
+            i % 3 * 3 + j
+        } else {
+            (3 - i % 3) % 3 * 3
+        }
+    };
+
+    complex((0..3).cartesian_product(0..3).flat_map(|(i, j)| {
+        let (a, b) = (vertex(i, j), vertex(i + 1, j + 1));
+        [[a, vertex(i + 1, j), b], [a, vertex(i, j + 1), b]]
+    }))
+}
+
+// Kühnel's 9-vertex complex projective plane. The vertices are the points of the affine plane over
+// F_3. For each direction, the facets are the unions of two parallel lines, given by the values c
+// and c + 2 of a linear form, minus a point of the first line.
+pub fn complex_projective_plane() -> SC {
+    let points = (0..3u32).cartesian_product(0..3u32).collect_vec();
+    let mut facets = Vec::<Vec<u32>>::new();
+    for (a, b) in [(0, 1), (1, 0), (1, 2), (1, 1)] {
+        let level = |(x, y): (u32, u32)| (a * x + b * y) % 3;
+        for c in 0..3 {
+            for removed in points.iter().filter(|p| level(**p) == c) {
+                facets.push(
+                    points
+                        .iter()
+                        .filter(|p| *p != removed && [c, (c + 2) % 3].contains(&level(**p)))
+                        .map(|(x, y)| 3 * x + y)
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    complex(facets)
+}
+
+// The lens space L(p, q) for p > 1, the quotient of the 3-sphere by the cyclic group of order p
+// generated by (z, w) -> (ζz, ζ^q w) with ζ = exp(2πi / p). The sphere is the join of two 2p-gons,
+// with vertices 0..2p and 2p..4p, which the generator rotates by 2 and 2q steps. The quotient of
+// the join is not a simplicial complex, but that of its barycentric subdivision is, as the vertices
+// of each simplex lie in different orbits.
+pub fn lens_space(p: u32, q: u32) -> SC {
+    assert![
+        p > 1 && p.gcd(&q) == 1,
+        "The parameters of a lens space should be coprime, and the first one should be at least 2."
+    ];
+    let m = 2 * p;
+    let rotate = |v: u32, t: u32| {
+        if v < m {
+            (v + 2 * t) % m
+        } else {
+            m + (v - m + 2 * (q % p) * t) % m
+        }
+    };
+    let join = (0..m)
+        .cartesian_product(0..m)
+        .map(|(i, j)| vec![i, (i + 1) % m, m + j, m + (j + 1) % m])
+        .collect_vec();
+
+    let mut orbits = Labels::new();
+    complex(subdivision(&join, |face| {
+        let images = (0..p).map(|t| face.iter().map(|v| rotate(*v, t)).sorted().collect_vec());
+        orbits.label(images.min().unwrap())
+    }))
+}
+
+// The Poincaré homology sphere, the quotient of the 3-sphere by the binary icosahedral group. The
+// 120 elements of the group are the vertices of the 600-cell, which the group permutes freely by
+// left multiplication. The quotient of the first barycentric subdivision of the 600-cell is not a
+// simplicial complex, since the group exchanges faces of the same simplex, but that of the second
+// one is.
+pub fn poincare_sphere() -> SC {
+    let group = binary_icosahedral_group();
+    let index: SCHashMap<Quaternion, u32> = group.iter().copied().zip(0..).collect();
+    let product = group
+        .iter()
+        .map(|g| group.iter().map(|h| index[&(*g * *h)]).collect_vec())
+        .collect_vec();
+    let one = index[&Quaternion::ONE];
+    let inverse = (0..group.len())
+        .map(|g| product[g].iter().position(|h| *h == one).unwrap() as u32)
+        .collect_vec();
+    let act = |g: u32, face: &[u32]| {
+        face.iter()
+            .map(|v| product[g as usize][*v as usize])
+            .sorted()
+            .collect_vec()
+    };
+
+    // The neighbors of a vertex are the 12 closest ones, and the simplices of the 600-cell are the
+    // cliques of its 1-skeleton. Every orbit of simplices meets the star of the identity.
+    let near = (0..group.len() as u32)
+        .filter(|g| group[*g as usize].0[0] == Golden(0, 1))
+        .collect_vec();
+    let adjacent =
+        |g: u32, h: u32| near.contains(&product[inverse[g as usize] as usize][h as usize]);
+    let star = near
+        .iter()
+        .copied()
+        .tuple_combinations()
+        .filter(|(a, b, c)| adjacent(*a, *b) && adjacent(*a, *c) && adjacent(*b, *c))
+        .map(|(a, b, c)| vec![one, a, b, c])
+        .collect_vec();
+
+    let mut faces = Labels::new();
+    let first = subdivision(&star, |face| faces.label(face.to_vec()));
+    let mut chains = Labels::new();
+    complex(subdivision(&first, |chain| {
+        let chain = chain.iter().map(|f| faces.key(*f).clone()).collect_vec();
+        // The images of the chain under the elements taking a vertex of its smallest face to the
+        // identity contain a representative of its orbit.
+        let smallest = chain.iter().min_by_key(|f| f.len()).unwrap().clone();
+        let image = smallest
+            .iter()
+            .map(|u| {
+                chain
+                    .iter()
+                    .map(|f| faces.label(act(inverse[*u as usize], f)))
+                    .sorted()
+                    .collect_vec()
+            })
+            .min()
+            .unwrap();
+        chains.label(image)
+    }))
+}
+
+// An 8-vertex dunce hat, a triangle whose sides are glued as a, a and a^-1. It is contractible, but
+// no edge lies in a single triangle, so it cannot be collapsed.
+pub fn dunce_hat() -> SC {
+    complex([
+        [0, 1, 3],
+        [0, 1, 4],
+        [0, 1, 5],
+        [0, 2, 5],
+        [0, 2, 6],
+        [0, 2, 7],
+        [0, 3, 4],
+        [0, 6, 7],
+        [1, 2, 3],
+        [1, 2, 4],
+        [1, 2, 6],
+        [1, 5, 6],
+        [2, 3, 5],
+        [2, 4, 7],
+        [3, 4, 5],
+        [4, 5, 6],
+        [4, 6, 7],
+    ])
+}
+
+// Bing's house with two rooms, built from unit squares in a 5 by 3 by 2 box, each split into two
+// triangles. The upper room is entered through a tunnel from the ground which crosses the lower
+// room, and the lower room through a tunnel from the roof which crosses the upper room. A wall in
+// each room joins the tunnel crossing it to an outer wall. Like the dunce hat, it is contractible
+// but cannot be collapsed.
+pub fn bings_house() -> SC {
+    let (lx, ly) = (5, 3);
+    let (up, down) = ((1, 1), (3, 1));
+    // Squares given by a corner and the two axes they span
+    let mut squares = Vec::<([u32; 3], usize, usize)>::new();
+    for (x, y) in (0..lx).cartesian_product(0..ly) {
+        // The ground, the roof and the floor between the rooms, with holes for the tunnels
+        if (x, y) != up {
+            squares.push(([x, y, 0], 0, 1));
+        }
+        if (x, y) != down {
+            squares.push(([x, y, 2], 0, 1));
+        }
+        if (x, y) != up && (x, y) != down {
+            squares.push(([x, y, 1], 0, 1));
+        }
+    }
+    for z in 0..2 {
+        for y in 0..ly {
+            squares.extend([([0, y, z], 1, 2), ([lx, y, z], 1, 2)]);
+        }
+        for x in 0..lx {
+            squares.extend([([x, 0, z], 0, 2), ([x, ly, z], 0, 2)]);
+        }
+    }
+    for ((x, y), z) in [(up, 0), (down, 1)] {
+        squares.extend([
+            ([x, y, z], 1, 2),
+            ([x + 1, y, z], 1, 2),
+            ([x, y, z], 0, 2),
+            ([x, y + 1, z], 0, 2),
+        ]);
+    }
+    squares.extend([([0, up.1, 0], 0, 2), ([down.0 + 1, down.1, 1], 0, 2)]);
+
+    complex(squares.into_iter().flat_map(|(p, a, b)| {
+        let corner = |da: u32, db: u32| {
+            let mut q = p;
+            q[a] += da;
+            q[b] += db;
+            (q[0] * (ly + 1) + q[1]) * 3 + q[2]
+        };
+        [
+            [corner(0, 0), corner(1, 0), corner(1, 1)],
+            [corner(0, 0), corner(0, 1), corner(1, 1)],
+        ]
+    }))
+}
+
+// The facets of the barycentric subdivision of the complex with the given facets, whose vertices
+// are the labels of its faces. Faces with the same label are identified.
+fn subdivision(facets: &[Vec<u32>], mut label: impl FnMut(&[u32]) -> u32) -> Vec<Vec<u32>> {
+    let mut subdivided = Vec::<Vec<u32>>::new();
+    for facet in facets {
+        for order in facet.iter().copied().permutations(facet.len()) {
+            subdivided.push(
+                (1..=order.len())
+                    .map(|k| label(&order[..k].iter().copied().sorted().collect_vec()))
+                    .collect(),
+            );
+        }
+    }
+
+    subdivided
+}
+
+// Labels distinct keys by 0, 1, ... in the order in which they are first seen
+struct Labels<T> {
+    labels: SCHashMap<T, u32>,
+    keys: Vec<T>,
+}
+
+impl<T: Clone + Eq + Hash> Labels<T> {
+    fn new() -> Self {
+        Self {
+            labels: new_hm(0),
+            keys: Vec::new(),
+        }
+    }
+
+    fn label(&mut self, key: T) -> u32 {
+        *self.labels.entry(key).or_insert_with_key(|key| {
+            self.keys.push(key.clone());
+            self.keys.len() as u32 - 1
+        })
+    }
+
+    fn key(&self, label: u32) -> &T {
+        &self.keys[label as usize]
+    }
+}
+
+// A number a + bφ, where φ is the golden ratio
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Golden(i32, i32);
+
+impl Add for Golden {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Neg for Golden {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0, -self.1)
+    }
+}
+
+// Since φ^2 = φ + 1
+impl Mul for Golden {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(
+            self.0 * rhs.0 + self.1 * rhs.1,
+            self.0 * rhs.1 + self.1 * rhs.0 + self.1 * rhs.1,
+        )
+    }
+}
+
+impl Golden {
+    fn half(self) -> Self {
+        assert![
+            self.0.is_even() && self.1.is_even(),
+            "Products of quaternions in the group should have half-integral coordinates."
+        ];
+
+        Self(self.0 / 2, self.1 / 2)
+    }
+}
+
+// A quaternion whose coordinates are halves of the given numbers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Quaternion([Golden; 4]);
+
+impl Quaternion {
+    const ONE: Self = Self([Golden(2, 0), Golden(0, 0), Golden(0, 0), Golden(0, 0)]);
+}
+
+impl Mul for Quaternion {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let ([a, b, c, d], [e, f, g, h]) = (self.0, rhs.0);
+
+        Self(
+            [
+                a * e + -(b * f) + -(c * g) + -(d * h),
+                a * f + b * e + c * h + -(d * g),
+                a * g + -(b * h) + c * e + d * f,
+                a * h + b * g + -(c * f) + d * e,
+            ]
+            .map(Golden::half),
+        )
+    }
+}
+
+// The 120 unit quaternions ±1, ±i, ±j, ±k, (±1 ± i ± j ± k) / 2 and the even permutations of
+// (0, ±1, ±φ, ±1/φ) / 2, where 1/φ = φ - 1
+fn binary_icosahedral_group() -> Vec<Quaternion> {
+    let signs = |n: usize| (0..n).map(|_| [1, -1]).multi_cartesian_product();
+    let mut group = Vec::<Quaternion>::new();
+    for (i, s) in (0..4).cartesian_product([2, -2]) {
+        let mut q = [Golden(0, 0); 4];
+        q[i] = Golden(s, 0);
+        group.push(Quaternion(q));
+    }
+    for s in signs(4) {
+        group.push(Quaternion([0, 1, 2, 3].map(|i| Golden(s[i], 0))));
+    }
+    let values = [Golden(0, 0), Golden(1, 0), Golden(0, 1), Golden(-1, 1)];
+    for perm in (0..4).permutations(4) {
+        let inversions = perm
+            .iter()
+            .tuple_combinations()
+            .filter(|(i, j)| i > j)
+            .count();
+        if inversions.is_odd() {
+            continue;
+        }
+        for s in signs(3) {
+            let signed = [
+                values[0],
+                values[1] * Golden(s[0], 0),
+                values[2] * Golden(s[1], 0),
+                values[3] * Golden(s[2], 0),
+            ];
+            group.push(Quaternion([0, 1, 2, 3].map(|i| signed[perm[i]])));
+        }
+    }
+
+    group
+}
@@ -0,0 +1,97 @@
+// Random complexes. Each is determined by its parameters and a seed, so that experiments and
+// benchmarks can be reproduced.
+use itertools::Itertools;
+
+use super::{SC, complex};
+use crate::helpers::{SCHashSet, new_hs};
+use crate::simplicial_complex::maximal_cliques;
+
+// The Linial–Meshulam complex Y_d(n, p): the full (d - 1)-skeleton of the simplex on n vertices,
+// with each face of dimension d added independently with probability p
+pub fn linial_meshulam_complex(n: u32, d: u32, p: f64, seed: u64) -> SC {
+    assert![
+        0 < d && d < n,
+        "The dimension of a Linial–Meshulam complex should be positive and less than its number of vertices."
+    ];
+    let mut rng = Rng::new(seed);
+    let top = (0..n)
+        .combinations(d as usize + 1)
+        .filter(|_| rng.chance(p))
+        .collect_vec();
+    // The faces of dimension d - 1 which are not in any face of dimension d are facets.
+    let covered: SCHashSet<Vec<u32>> = top
+        .iter()
+        .flat_map(|f| f.iter().copied().combinations(d as usize))
+        .collect();
+    let rest = (0..n)
+        .combinations(d as usize)
+        .filter(|f| !covered.contains(f));
+
+    complex(top.into_iter().chain(rest))
+}
+
+// The clique complex of the Erdős–Rényi graph G(n, p), in which each edge is present
+// independently with probability p
+pub fn random_flag_complex(n: u32, p: f64, seed: u64) -> SC {
+    let mut rng = Rng::new(seed);
+    let mut neighbors = vec![new_hs::<u32>(0); n as usize];
+    for (v, w) in (0..n).tuple_combinations() {
+        if rng.chance(p) {
+            neighbors[v as usize].insert(w);
+            neighbors[w as usize].insert(v);
+        }
+    }
+
+    complex(maximal_cliques(&neighbors))
+}
+
+// A complex with `count` random faces, each made of `size` distinct vertices chosen uniformly among
+// n, of which the maximal ones are the facets
+pub fn random_facet_complex(n: u32, count: usize, size: u32, seed: u64) -> SC {
+    assert![
+        size <= n,
+        "The faces of a random complex cannot have more vertices than the complex."
+    ];
+    let mut rng = Rng::new(seed);
+
+    complex((0..count).map(|_| {
+        // Floyd's algorithm, which draws each vertex once
+        let mut face = new_hs::<u32>(size as usize);
+        for j in n - size..n {
+            let v = rng.below(j as u64 + 1) as u32;
+            if !face.insert(v) {
+                face.insert(j);
+            }
+        }
+
+        face
+    }))
+}
+
+// The SplitMix64 generator, whose output only depends on the seed
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+        z ^ (z >> 31)
+    }
+
+    // Whether an event of probability p happens
+    fn chance(&mut self, p: f64) -> bool {
+        ((self.next() >> 11) as f64) / ((1u64 << 53) as f64) < p
+    }
+
+    // A number in 0..n
+    fn below(&mut self, n: u64) -> u64 {
+        ((self.next() as u128 * n as u128) >> 64) as u64
+    }
+}
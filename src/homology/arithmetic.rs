@@ -1,139 +1,167 @@
-fn to_binary(n: Point) -> SCHashSet<Point> {
-    let mut next_power = n.next_power_of_two();
-    let mut logs = new_hs::<Point>(next_power.ilog2());
-    while n != 0 {
-        let prev_power = next_power / 2;
-        powers.insert(prev_power.ilog2());
-        n -= prev_power;
-        next_power = n.next_power_of_two();
-    }
+// Linear algebra over the prime fields F_p
+
+use crate::helpers::{SCHashMap, new_hm};
 
-    logs
+pub fn is_prime(p: u32) -> bool {
+    p >= 2
+        && (2..)
+            .take_while(|d| *d <= p / d)
+            .all(|d| !p.is_multiple_of(d))
 }
 
-fn mod_exp(b: Point, e: Point, m: Point) -> Point {
-    let mut logs = to_binary(e);
-    let mut i: Point = 1;
-    let mut power: Point = b;
-    let mut res: Point = 1;
-    while !powers.is_empty() {
-        if logs.remove(i) {
-            res *= power;
-            res %= m;
+pub(crate) fn mod_exp(b: u32, mut e: u32, m: u32) -> u32 {
+    let m = m as u64;
+    let mut power = b as u64 % m;
+    let mut res = 1;
+    while e > 0 {
+        if e & 1 == 1 {
+            res = res * power % m;
         }
-        i += 1;
-        power *= power;
-        power %= m;
+        power = power * power % m;
+        e >>= 1;
     }
 
-    res
+    res as u32
 }
 
-fn mod_recip(n: Point, m: Point) -> Point {
+// The inverse of `n` in F_m, for `m` prime and `n` not divisible by `m`.
+pub(crate) fn mod_recip(n: u32, m: u32) -> u32 {
     mod_exp(n, m - 2, m)
 }
 
-
-trait Vector {
-    fn is_empty(&self) -> bool;
-    fn contains(&self, idx: Point) -> bool;
-    fn grab_key(&self) -> Option(Point);
-    fn entry_to_one(&mut self, idx: Point);
-    fn reduce_other(&self, other: &mut Self);
+pub(crate) fn mod_mul(a: u32, b: u32, m: u32) -> u32 {
+    (a as u64 * b as u64 % m as u64) as u32
 }
 
-struct ModVector {
-    modulus: Point;
-    entries: SCHashMap<Point, Point>;
-};
-
-impl Vector for ModVector {
-    fn contains(&self, idx: Point) -> bool {
-        self.entries.contains_key(idx)
-    }
+// A sparse vector over F_p, stored as pairs of indices and nonzero coefficients sorted by index.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SparseVector(pub(crate) Vec<(usize, u32)>);
 
-    fn entry_to_one(&mut self, idx: Point) {
-        // assert![self.contains_key(idx), "The index was not found in the vector"];
-        let inv = mod_recip(self.entries[idx], self.modulus);
-        for (_, val) in &mut self.entries {
-            val *= inv;
-            val %= self.modulus;
-        }
+impl SparseVector {
+    // The entry with the greatest index
+    pub(crate) fn pivot(&self) -> Option<(usize, u32)> {
+        self.0.last().copied()
     }
 
-    fn reduce_other(&self, other: &mut Self) {
-        for (key, other_val) in other.entries {
-            if let Some(val) = self.entries.get(key) {
-                other_val -= val;
-                other_val %= other.modulus;
+    // Replace `self` with `self + c * other` in F_m.
+    pub(crate) fn add_multiple(&mut self, other: &Self, c: u32, m: u32) {
+        let mut sum = Vec::with_capacity(self.0.len() + other.0.len());
+        let (mut lhs, mut rhs) = (self.0.iter().peekable(), other.0.iter().peekable());
+        loop {
+            match (lhs.peek(), rhs.peek()) {
+                (Some((i, a)), Some((j, b))) if i == j => {
+                    let entry = (a + mod_mul(c, *b, m)) % m;
+                    if entry != 0 {
+                        sum.push((*i, entry));
+                    }
+                    lhs.next();
+                    rhs.next();
+                }
+                (Some((i, a)), Some((j, _))) if i < j => {
+                    sum.push((*i, *a));
+                    lhs.next();
+                }
+                (_, Some((j, b))) => {
+                    let entry = mod_mul(c, *b, m);
+                    if entry != 0 {
+                        sum.push((*j, entry));
+                    }
+                    rhs.next();
+                }
+                (Some((i, a)), None) => {
+                    sum.push((*i, *a));
+                    lhs.next();
+                }
+                (None, None) => break,
             }
         }
-    }
-}
 
-struct ModTwoVector(SCHashSet<Point>);
-
-impl Vector for ModTwoVector {
-    fn contains(&self, idx: Point) -> bool {
-        self.0.contains(idx)
+        self.0 = sum;
     }
+}
 
-    fn entry_to_one(&mut self, idx: Point) {
-        // assert![self.contains(idx), "The index was not found in the vector"];
+// Reduce the columns of a matrix over F_m from left to right until the nonzero columns have
+// distinct pivots. Return a map from the pivots to the indices of the columns which have them.
+pub(crate) fn reduce(cols: &mut [SparseVector], m: u32) -> SCHashMap<usize, usize> {
+    let mut pivots = new_hm::<usize, usize>(cols.len());
+    for j in 0..cols.len() {
+        while let Some((row, entry)) = cols[j].pivot() {
+            let Some(&i) = pivots.get(&row) else {
+                pivots.insert(row, j);
+                break;
+            };
+            let [col, other] = cols.get_disjoint_mut([j, i]).unwrap();
+            let (_, other_entry) = other.pivot().unwrap();
+            let c = m - mod_mul(entry, mod_recip(other_entry, m), m);
+            col.add_multiple(other, c, m);
+        }
     }
 
-    fn reduce_other(&self, other: &mut Self) {
-        other ^= self;
-    }
+    pivots
 }
 
+// The columns of a matrix over F_m reduced from left to right, each with the combination of
+// columns of another matrix which it is the image of, e.g. the chain it is the boundary of
+pub(crate) struct Reduction {
+    modulus: u32,
+    cols: Vec<(SparseVector, SparseVector)>,
+    pivots: SCHashMap<usize, usize>,
+}
 
-trait Matrix {
-    type Col: Vector;
-
-    fn get_mut_cols(&mut self) -> &mut Vec<Self::Col>;
-
-    fn domain_dimension(&mut self) -> Point {
-        self.get_mut_cols().len()
+impl Reduction {
+    pub(crate) fn new(modulus: u32) -> Self {
+        Self {
+            modulus,
+            cols: Vec::new(),
+            pivots: new_hm(0),
+        }
     }
 
-    fn rank(&mut self) -> Point {
-        let mut rank = 0;
-        let mut cols = self.get_mut_cols();
-        cols = cols.iter().filter(|v| !v.is_empty()).copied().collect();
-        while let Some(mut col) = cols.pop() {
-            // Define `grab_entry`
-            if let Some(key) = col.grab_entry() {
-                rank += 1;
-                col.entry_to_one(key);
-                for other in &mut cols {
-                    if other.contains(key) {
-                        col.nerve_reduce_other(&mut other);
-                    }
-                }
-            }
+    // Reduce `col`, the image of `combination`, against the columns, and return what is left of
+    // it with the combination it is now the image of.
+    pub(crate) fn reduce(
+        &self,
+        mut col: SparseVector,
+        mut combination: SparseVector,
+    ) -> (SparseVector, SparseVector) {
+        let m = self.modulus;
+        while let Some((row, entry)) = col.pivot() {
+            let Some(&j) = self.pivots.get(&row) else {
+                break;
+            };
+            let (other, other_combination) = &self.cols[j];
+            let c = m - mod_mul(entry, mod_recip(other.pivot().unwrap().1, m), m);
+            col.add_multiple(other, c, m);
+            combination.add_multiple(other_combination, c, m);
         }
 
-        rank
+        (col, combination)
     }
-}
 
-struct ModMatrix(SCHashMap<Point, ModVector>);
+    // Add a reduced column, unless it vanishes. Return whether it was added.
+    pub(crate) fn push(&mut self, col: SparseVector, combination: SparseVector) -> bool {
+        let Some((row, _)) = col.pivot() else {
+            return false;
+        };
+        self.pivots.insert(row, self.cols.len());
+        self.cols.push((col, combination));
 
-impl Matrix for ModMatrix {
-    type Col = ModVector;
-
-    fn get_mut_cols(&mut self) -> &mut Vec<Self::Col> {
-        self.0.values().collect()
+        true
     }
 }
 
-struct ModTwoMatrix(SCHashMap<Point, ModTwoVector>);
-
-impl Matrix for ModTwoMatrix {
-    type Col = ModTwoVector;
-
-    fn get_mut_cols(&mut self) -> &mut Vec<Self::Col> {
-        self.0.values().collect()
+// A basis of the kernel of the matrix with the given columns over F_m
+pub(crate) fn kernel(cols: &[SparseVector], m: u32) -> Vec<SparseVector> {
+    let mut reduction = Reduction::new(m);
+    let mut kernel = Vec::new();
+    for (i, col) in cols.iter().enumerate() {
+        let (col, combination) = reduction.reduce(col.clone(), SparseVector(vec![(i, 1)]));
+        if col.pivot().is_none() {
+            kernel.push(combination);
+        } else {
+            reduction.push(col, combination);
+        }
     }
+
+    kernel
 }
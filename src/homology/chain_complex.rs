@@ -1,19 +1,171 @@
-struct BiComplex {
-    cells: Vec<SCHashSet<Point>>;
-    faces: SCHashMap<Point, SCHashMap<Point, bool>>;
-    cofaces: SCHashMap<Point, SCHashSet<Point>>;
+use super::arithmetic::{Reduction, SparseVector, kernel, reduce};
+
+use crate::helpers::{SCHashMap, new_hm};
+use crate::{SimplicialComplex, Vertex};
+
+// The simplicial chain complex of a complex, or of a pair of complexes, over F_p. Every face of
+// the complex is enumerated, so this should only be used for small complexes.
+pub(crate) struct ChainComplex<Point: Vertex> {
+    modulus: u32,
+    // `cells[k]` lists the faces with `k` vertices as sorted tuples; the empty face is only
+    // included when computing reduced homology.
+    cells: Vec<Vec<Vec<Point>>>,
+    indices: Vec<SCHashMap<Vec<Point>, usize>>,
 }
 
-trait ChainComplex;
+impl<Point: Vertex> ChainComplex<Point> {
+    // The augmented chain complex of `sc`, whose homology is the reduced homology of `sc`
+    pub(crate) fn reduced(sc: &SimplicialComplex<Point>, modulus: u32) -> Self {
+        let mut cc = Self::unreduced(sc, modulus);
+        cc.insert(Vec::new());
+
+        cc
+    }
+
+    pub(crate) fn unreduced(sc: &SimplicialComplex<Point>, modulus: u32) -> Self {
+        // The facets need not be sorted by size, e.g. in the input.
+        let height = (sc.dimension() + 1) as usize;
+        let mut cc = Self {
+            modulus,
+            cells: vec![Vec::new(); height + 1],
+            indices: vec![new_hm(0); height + 1],
+        };
+        for facet in sc {
+            let tuple = facet.tuple();
+            assert![
+                tuple.len() < 64,
+                "Homology can only be computed for complexes of dimension less than 63"
+            ];
+            for mask in 1..1u64 << tuple.len() {
+                cc.insert(subtuple(&tuple, mask));
+            }
+        }
+
+        cc
+    }
+
+    // The relative chain complex of the pair `(sc, sub)`. If `sub` is nonempty, its homology is
+    // the reduced homology of the quotient of `sc` by `sub`.
+    pub(crate) fn relative(
+        sc: &SimplicialComplex<Point>,
+        sub: &SimplicialComplex<Point>,
+        modulus: u32,
+    ) -> Self {
+        let mut cc = Self::unreduced(sc, modulus);
+        let mut sub_cells = Self::unreduced(sub, modulus);
+        if !sub.is_empty() {
+            sub_cells.insert(Vec::new());
+        }
+        for k in 0..sub_cells.cells.len().min(cc.cells.len()) {
+            cc.cells[k].retain(|f| !sub_cells.indices[k].contains_key(f));
+            cc.reindex(k);
+        }
+
+        cc
+    }
+
+    fn insert(&mut self, tuple: Vec<Point>) {
+        let k = tuple.len();
+        if !self.indices[k].contains_key(&tuple) {
+            self.indices[k].insert(tuple.clone(), self.cells[k].len());
+            self.cells[k].push(tuple);
+        }
+    }
+
+    fn reindex(&mut self, k: usize) {
+        self.indices[k] = self.cells[k].iter().cloned().zip(0..).collect();
+    }
+
+    // The greatest number of vertices of a cell
+    pub(crate) fn height(&self) -> usize {
+        self.cells.len() - 1
+    }
 
-struct ModChainComplex;
+    pub(crate) fn cells(&self, k: usize) -> &[Vec<Point>] {
+        self.cells.get(k).map_or(&[], |c| c)
+    }
 
-impl ChainComplex for ModChainComplex;
+    pub(crate) fn index(&self, tuple: &[Point]) -> Option<usize> {
+        self.indices.get(tuple.len())?.get(tuple).copied()
+    }
 
-impl From<BiComplex> for
+    // The boundary of a cell with `k` vertices, expressed in the cells with `k - 1` vertices.
+    // Faces which are not cells (e.g. faces of the subcomplex in a relative chain complex) are
+    // dropped.
+    pub(crate) fn boundary_of(&self, tuple: &[Point]) -> SparseVector {
+        let m = self.modulus;
+        let mut col: Vec<(usize, u32)> = (0..tuple.len())
+            .filter_map(|i| {
+                let mut face = tuple.to_vec();
+                face.remove(i);
+                let sign = if i % 2 == 0 { 1 } else { m - 1 };
 
-struct ModTwoChainComplex;
+                self.index(&face).map(|j| (j, sign % m))
+            })
+            .collect();
+        col.sort_unstable();
 
-impl ChainComplex for ModTwoChainComplex;
+        SparseVector(col)
+    }
 
-impl From<BiComplex> for ModTwoChainComplex;
+    // The columns of the boundary map from the cells with `k` vertices
+    pub(crate) fn boundary(&self, k: usize) -> Vec<SparseVector> {
+        if k == 0 {
+            return vec![SparseVector::default(); self.cells(0).len()];
+        }
+
+        self.cells(k).iter().map(|t| self.boundary_of(t)).collect()
+    }
+
+    pub(crate) fn rank(&self, k: usize) -> usize {
+        reduce(&mut self.boundary(k), self.modulus).len()
+    }
+
+    // The Betti numbers, indexed by the number of vertices of the cells rather than by dimension
+    pub(crate) fn cell_betti_numbers(&self) -> Vec<usize> {
+        let ranks: Vec<usize> = (0..=self.height() + 1).map(|k| self.rank(k)).collect();
+
+        (0..=self.height())
+            .map(|k| self.cells(k).len() - ranks[k] - ranks[k + 1])
+            .collect()
+    }
+
+    // Representatives of a basis of the homology, indexed by the number of vertices of the cells.
+    // The cycles which are independent of the boundaries and the previous cycles form the basis.
+    pub(crate) fn cycles(&self) -> Vec<Vec<SparseVector>> {
+        (0..=self.height())
+            .map(|k| {
+                let mut reduction = Reduction::new(self.modulus);
+                for col in self.boundary(k + 1) {
+                    let (col, _) = reduction.reduce(col, SparseVector::default());
+                    reduction.push(col, SparseVector::default());
+                }
+                let mut basis = Vec::new();
+                for cycle in kernel(&self.boundary(k), self.modulus) {
+                    let (col, _) = reduction.reduce(cycle.clone(), SparseVector::default());
+                    if reduction.push(col, SparseVector::default()) {
+                        basis.push(cycle);
+                    }
+                }
+
+                basis
+            })
+            .collect()
+    }
+
+    // The chain with the given coefficients on the cells with `k` vertices
+    pub(crate) fn chain(&self, k: usize, col: &SparseVector) -> Vec<(Vec<Point>, u32)> {
+        col.0
+            .iter()
+            .map(|(i, c)| (self.cells(k)[*i].clone(), *c))
+            .collect()
+    }
+}
+
+// The entries of `tuple` selected by the bits of `mask`
+pub(crate) fn subtuple<Point: Copy>(tuple: &[Point], mask: u64) -> Vec<Point> {
+    (0..tuple.len())
+        .filter(|i| mask >> i & 1 == 1)
+        .map(|i| tuple[i])
+        .collect()
+}
@@ -0,0 +1,131 @@
+use itertools::Itertools;
+
+use super::arithmetic::{Reduction, SparseVector, kernel};
+use super::chain_complex::ChainComplex;
+
+use crate::{SimplicialComplex, Vertex};
+
+// The cohomology ring of a complex over F_2, with a representative cocycle for each class of a
+// basis. Cochains are sparse vectors whose entries are all 1, indexed by the cells of their
+// degree in the chain complex. Every face is enumerated, so this should only be used for small
+// complexes, such as the output of `sc-simplify`.
+pub struct Cohomology<Point: Vertex> {
+    cc: ChainComplex<Point>,
+    // `basis[k]` lists the representatives of a basis of the cohomology of degree `k - 1`, whose
+    // cochains are indexed by the cells with `k` vertices.
+    basis: Vec<Vec<SparseVector>>,
+    // `reduced[k]` holds the coboundaries into the cells with `k` vertices and the basis cocycles,
+    // after reduction, with the classes of the basis each of them sums to.
+    reduced: Vec<Reduction>,
+}
+
+impl<Point: Vertex> Cohomology<Point> {
+    pub(crate) fn new(sc: &SimplicialComplex<Point>) -> Self {
+        let cc = ChainComplex::unreduced(sc, 2);
+        let height = cc.height();
+        // `coboundaries[k]` lists the coboundaries of the cells with `k` vertices.
+        let mut coboundaries: Vec<Vec<SparseVector>> = (0..=height + 1)
+            .map(|k| vec![SparseVector::default(); cc.cells(k).len()])
+            .collect();
+        for k in 1..=height {
+            for (i, col) in cc.boundary(k).into_iter().enumerate() {
+                for (j, _) in col.0 {
+                    coboundaries[k - 1][j].0.push((i, 1));
+                }
+            }
+        }
+
+        let mut basis = vec![Vec::new(); height + 1];
+        let mut reduced: Vec<Reduction> = (0..=height).map(|_| Reduction::new(2)).collect();
+        for k in 1..=height {
+            for col in &coboundaries[k - 1] {
+                let (col, _) = reduced[k].reduce(col.clone(), SparseVector::default());
+                reduced[k].push(col, SparseVector::default());
+            }
+            // A cocycle which is independent of the coboundaries and the previous cocycles is
+            // added to the basis.
+            for cocycle in kernel(&coboundaries[k], 2) {
+                let (col, mut classes) =
+                    reduced[k].reduce(cocycle.clone(), SparseVector::default());
+                if col.pivot().is_some() {
+                    classes.add_multiple(&SparseVector(vec![(basis[k].len(), 1)]), 1, 2);
+                    basis[k].push(cocycle);
+                    reduced[k].push(col, classes);
+                }
+            }
+        }
+
+        Self { cc, basis, reduced }
+    }
+
+    // The Betti numbers, indexed by degree
+    pub fn betti_numbers(&self) -> Vec<usize> {
+        self.basis.iter().skip(1).map(|b| b.len()).collect()
+    }
+
+    // The faces on which the representative of the `i`-th class of the basis in `degree` is 1
+    pub fn cocycle(&self, degree: usize, i: usize) -> Vec<Vec<Point>> {
+        let cells = self.cc.cells(degree + 1);
+
+        self.basis[degree + 1][i]
+            .0
+            .iter()
+            .map(|(j, _)| cells[*j].clone())
+            .sorted()
+            .collect()
+    }
+
+    // The classes of the basis in `degree` whose sum is the class of the cocycle which is 1 on
+    // the given faces. Panic if it is not a cocycle.
+    pub fn coordinates(&self, degree: usize, faces: &[Vec<Point>]) -> Vec<usize> {
+        let mut col = faces
+            .iter()
+            .map(|f| {
+                let j = self
+                    .cc
+                    .index(f)
+                    .expect("A cochain should only take values on faces of the complex.");
+                (j, 1)
+            })
+            .collect_vec();
+        col.sort_unstable();
+
+        self.classes(degree + 1, SparseVector(col))
+    }
+
+    fn classes(&self, k: usize, cocycle: SparseVector) -> Vec<usize> {
+        if k >= self.reduced.len() {
+            return Vec::new();
+        }
+        let (col, classes) = self.reduced[k].reduce(cocycle, SparseVector::default());
+        assert![col.pivot().is_none(), "A cochain should be a cocycle."];
+
+        classes.0.iter().map(|(i, _)| *i).collect()
+    }
+
+    // The cup product of the `i`-th class of the basis in degree `p` and the `j`-th class in
+    // degree `q`, given by the classes of the basis in degree `p + q` whose sum it is. The cup
+    // product of cocycles is 1 on a face if the first is 1 on its front face with `p + 1`
+    // vertices and the second is 1 on its back face with `q + 1` vertices.
+    pub fn cup(&self, (p, i): (usize, usize), (q, j): (usize, usize)) -> Vec<usize> {
+        let k = p + q + 1;
+        let front = &self.basis[p + 1][i];
+        let back = &self.basis[q + 1][j];
+        let product = self
+            .cc
+            .cells(k)
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| {
+                let in_support = |cocycle: &SparseVector, face: &[Point]| {
+                    let index = self.cc.index(face).unwrap();
+                    cocycle.0.binary_search_by_key(&index, |(i, _)| *i).is_ok()
+                };
+                in_support(front, &f[..=p]) && in_support(back, &f[p..])
+            })
+            .map(|(j, _)| (j, 1))
+            .collect();
+
+        self.classes(k, SparseVector(product))
+    }
+}
@@ -0,0 +1,110 @@
+use super::arithmetic::{SparseVector, reduce};
+
+use crate::SymmetricComplex;
+use crate::helpers::{SCHashMap, new_hm};
+
+// The chain complex of coinvariants of a complex with a group action over F_p, whose cells are the
+// orbits of faces. A face is identified with each of its images, with the sign of the permutation
+// of its vertices, so over fields of odd characteristic the orbits of faces which some permutation
+// maps to themselves with the opposite orientation vanish. When p does not divide the order of the
+// group, the homology of this complex is the invariant part of the homology of the complex, which
+// is also the homology of the quotient space.
+pub(crate) struct CoinvariantComplex<'a> {
+    sc: &'a SymmetricComplex,
+    modulus: u32,
+    // `cells[k]` lists the representatives of the orbits of faces with `k` vertices; the empty
+    // face is only included when computing reduced homology.
+    cells: Vec<Vec<Vec<u32>>>,
+    indices: Vec<SCHashMap<Vec<u32>, usize>>,
+}
+
+impl<'a> CoinvariantComplex<'a> {
+    pub(crate) fn new(sc: &'a SymmetricComplex, modulus: u32, reduced: bool) -> Self {
+        let mut cells: Vec<Vec<Vec<u32>>> = sc
+            .face_orbits()
+            .into_iter()
+            .map(|orbits| {
+                orbits
+                    .into_iter()
+                    .filter(|o| o.orientable || modulus == 2)
+                    .map(|o| o.representative)
+                    .collect()
+            })
+            .collect();
+        if reduced {
+            cells[0].push(Vec::new());
+        }
+        let indices = cells
+            .iter()
+            .map(|c| {
+                let mut index = new_hm(c.len());
+                index.extend(c.iter().cloned().zip(0..));
+                index
+            })
+            .collect();
+
+        Self {
+            sc,
+            modulus,
+            cells,
+            indices,
+        }
+    }
+
+    fn cells(&self, k: usize) -> &[Vec<u32>] {
+        self.cells.get(k).map_or(&[], |c| c)
+    }
+
+    // The boundary of the orbit of a face with `k` vertices. Each face of the representative is
+    // replaced by the representative of its orbit, with the orientation it is mapped to.
+    fn boundary_of(&self, tuple: &[u32]) -> SparseVector {
+        let m = self.modulus;
+        let mut col: Vec<(usize, u32)> = (0..tuple.len())
+            .filter_map(|i| {
+                let mut face = tuple.to_vec();
+                face.remove(i);
+                let orbit = self.sc.orbit(&face);
+                let j = *self.indices[face.len()].get(&orbit.representative)?;
+                let sign = if (i % 2 == 1) != orbit.reversed {
+                    m - 1
+                } else {
+                    1
+                };
+
+                Some((j, sign % m))
+            })
+            .collect();
+        // Several faces of the representative may lie in the same orbit.
+        col.sort_unstable();
+        let mut merged = Vec::<(usize, u32)>::with_capacity(col.len());
+        for (j, c) in col {
+            match merged.last_mut() {
+                Some((i, d)) if *i == j => *d = (*d + c) % m,
+                _ => merged.push((j, c)),
+            }
+        }
+        merged.retain(|(_, c)| *c != 0);
+
+        SparseVector(merged)
+    }
+
+    fn rank(&self, k: usize) -> usize {
+        if k == 0 {
+            return 0;
+        }
+        let mut cols: Vec<SparseVector> =
+            self.cells(k).iter().map(|t| self.boundary_of(t)).collect();
+
+        reduce(&mut cols, self.modulus).len()
+    }
+
+    // The Betti numbers, indexed by the number of vertices of the cells rather than by dimension
+    pub(crate) fn cell_betti_numbers(&self) -> Vec<usize> {
+        let height = self.cells.len() - 1;
+        let ranks: Vec<usize> = (0..=height + 1).map(|k| self.rank(k)).collect();
+
+        (0..=height)
+            .map(|k| self.cells(k).len() - ranks[k] - ranks[k + 1])
+            .collect()
+    }
+}
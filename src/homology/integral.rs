@@ -0,0 +1,227 @@
+use itertools::Itertools;
+
+use super::chain_complex::ChainComplex;
+
+use crate::Vertex;
+use crate::helpers::{SCHashSet, new_hs};
+
+// A finitely generated abelian group `Z^rank + Z/t_1 + ... + Z/t_n`, where each of the torsion
+// coefficients `t_i` is greater than 1 and divides the next
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AbelianGroup {
+    pub rank: usize,
+    pub torsion: Vec<u64>,
+}
+
+impl AbelianGroup {
+    pub fn is_trivial(&self) -> bool {
+        self.rank == 0 && self.torsion.is_empty()
+    }
+
+    pub fn is_free(&self) -> bool {
+        self.torsion.is_empty()
+    }
+}
+
+// A column of an integer matrix, sorted by row
+type IntegerVector = Vec<(usize, i64)>;
+
+// The homology groups over the integers of a chain complex, indexed by the number of vertices of
+// the cells. They are read off the elementary divisors of the boundary matrices: the divisors of
+// the boundary from the cells with `k` vertices give its rank, and those greater than 1 give the
+// torsion of the homology of the cells with `k - 1` vertices.
+pub(crate) fn homology<Point: Vertex>(cc: &ChainComplex<Point>) -> Vec<AbelianGroup> {
+    let divisors: Vec<Vec<u64>> = (0..=cc.height() + 1)
+        .map(|k| {
+            if k == 0 {
+                return Vec::new();
+            }
+            let cols = cc
+                .cells(k)
+                .iter()
+                .map(|t| {
+                    let mut col: IntegerVector = (0..t.len())
+                        .filter_map(|i| {
+                            let mut face = t.to_vec();
+                            face.remove(i);
+                            let sign = if i % 2 == 0 { 1 } else { -1 };
+
+                            cc.index(&face).map(|j| (j, sign))
+                        })
+                        .collect();
+                    col.sort_unstable();
+                    col
+                })
+                .collect();
+
+            elementary_divisors(cols, cc.cells(k - 1).len())
+        })
+        .collect();
+
+    (0..=cc.height())
+        .map(|k| AbelianGroup {
+            rank: cc.cells(k).len() - divisors[k].len() - divisors[k + 1].len(),
+            torsion: divisors[k + 1].iter().copied().filter(|d| *d > 1).collect(),
+        })
+        .collect()
+}
+
+// The nonzero diagonal entries of the Smith normal form of the matrix with the given columns, in
+// increasing order of divisibility. Boundary matrices have many entries ±1, so pivots ±1 are
+// first eliminated from the sparse matrix. A column with such a pivot is subtracted from the
+// others to clear the row of the pivot, after which the pivot is alone in its row and column. The
+// rest is reduced as a dense matrix.
+fn elementary_divisors(mut cols: Vec<IntegerVector>, row_count: usize) -> Vec<u64> {
+    // `rows[i]` holds the columns which are nonzero in row `i`.
+    let mut rows: Vec<SCHashSet<usize>> = (0..row_count).map(|_| new_hs(0)).collect();
+    for (j, col) in cols.iter().enumerate() {
+        for (i, _) in col {
+            rows[*i].insert(j);
+        }
+    }
+
+    let mut units = 0;
+    let mut progress = true;
+    while progress {
+        progress = false;
+        for j in 0..cols.len() {
+            // The unit in the sparsest row makes for the least fill-in.
+            let Some((i, u)) = cols[j]
+                .iter()
+                .filter(|(_, a)| a.abs() == 1)
+                .min_by_key(|(i, _)| rows[*i].len())
+                .copied()
+            else {
+                continue;
+            };
+            let pivot = std::mem::take(&mut cols[j]);
+            for (r, _) in &pivot {
+                rows[*r].remove(&j);
+            }
+            for other in rows[i].iter().copied().collect_vec() {
+                let a = cols[other][cols[other].binary_search_by_key(&i, |(r, _)| *r).unwrap()].1;
+                let before: SCHashSet<usize> = cols[other].iter().map(|(r, _)| *r).collect();
+                cols[other] = add_multiple(&cols[other], &pivot, -a * u);
+                let after: SCHashSet<usize> = cols[other].iter().map(|(r, _)| *r).collect();
+                for r in before.difference(&after) {
+                    rows[*r].remove(&other);
+                }
+                for r in after.difference(&before) {
+                    rows[*r].insert(other);
+                }
+            }
+            units += 1;
+            progress = true;
+        }
+    }
+
+    // The remaining rows and columns, as a dense matrix
+    let remaining_rows: Vec<usize> = (0..row_count).filter(|i| !rows[*i].is_empty()).collect();
+    let remaining_cols = cols.iter().filter(|c| !c.is_empty()).collect_vec();
+    let mut matrix = vec![vec![0; remaining_cols.len()]; remaining_rows.len()];
+    for (j, col) in remaining_cols.iter().enumerate() {
+        for (i, a) in col.iter() {
+            matrix[remaining_rows.binary_search(i).unwrap()][j] = *a;
+        }
+    }
+
+    let mut divisors = vec![1; units];
+    divisors.extend(smith_diagonal(matrix));
+
+    divisors
+}
+
+// `col + c * other`
+fn add_multiple(col: &[(usize, i64)], other: &[(usize, i64)], c: i64) -> IntegerVector {
+    col.iter()
+        .copied()
+        .merge_join_by(
+            other.iter().map(|(i, a)| (*i, checked_mul(c, *a))),
+            |x, y| x.0.cmp(&y.0),
+        )
+        .map(|e| e.reduce(|x, y| (x.0, checked_add(x.1, y.1))))
+        .filter(|(_, a)| *a != 0)
+        .collect()
+}
+
+// The nonzero diagonal entries of the Smith normal form of a dense matrix. The entry of least
+// absolute value is moved to the corner and its row and column are cleared by Euclidean steps,
+// moving any smaller remainder to the corner. If an entry of the rest is not divisible by the
+// corner, its row is added to the row of the corner to continue the steps.
+fn smith_diagonal(mut a: Vec<Vec<i64>>) -> Vec<u64> {
+    let rows = a.len();
+    let cols = a.first().map_or(0, |r| r.len());
+    let mut divisors = Vec::new();
+    for t in 0..rows.min(cols) {
+        let Some((i, j)) = (t..rows)
+            .cartesian_product(t..cols)
+            .filter(|(i, j)| a[*i][*j] != 0)
+            .min_by_key(|(i, j)| a[*i][*j].unsigned_abs())
+        else {
+            break;
+        };
+        a.swap(t, i);
+        for row in &mut a {
+            row.swap(t, j);
+        }
+
+        loop {
+            let mut cleared = true;
+            for i in t + 1..rows {
+                let q = a[i][t] / a[t][t];
+                if q != 0 {
+                    add_row(&mut a, i, t, -q);
+                }
+                if a[i][t] != 0 {
+                    a.swap(t, i);
+                    cleared = false;
+                }
+            }
+            for j in t + 1..cols {
+                let q = a[t][j] / a[t][t];
+                if q != 0 {
+                    for row in a.iter_mut().skip(t) {
+                        row[j] = checked_add(row[j], checked_mul(-q, row[t]));
+                    }
+                }
+                if a[t][j] != 0 {
+                    for row in &mut a {
+                        row.swap(t, j);
+                    }
+                    cleared = false;
+                }
+            }
+            if !cleared {
+                continue;
+            }
+
+            let corner = a[t][t];
+            let Some(i) = (t + 1..rows).find(|i| a[*i][t + 1..].iter().any(|x| x % corner != 0))
+            else {
+                break;
+            };
+            add_row(&mut a, t, i, 1);
+        }
+        divisors.push(a[t][t].unsigned_abs());
+    }
+
+    divisors
+}
+
+// Add `c` times the row `source` to the row `target`.
+fn add_row(a: &mut [Vec<i64>], target: usize, source: usize, c: i64) {
+    let source = a[source].clone();
+    for (x, y) in a[target].iter_mut().zip(source) {
+        *x = checked_add(*x, checked_mul(c, y));
+    }
+}
+
+fn checked_add(a: i64, b: i64) -> i64 {
+    a.checked_add(b)
+        .expect("Integral homology overflowed 64-bit integers.")
+}
+
+fn checked_mul(a: i64, b: i64) -> i64 {
+    a.checked_mul(b)
+        .expect("Integral homology overflowed 64-bit integers.")
+}
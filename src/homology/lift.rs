@@ -0,0 +1,237 @@
+use std::collections::hash_map::Entry;
+
+use itertools::Itertools;
+
+use super::Chain;
+use super::arithmetic::{Reduction, SparseVector, mod_mul};
+use crate::helpers::{SCHashMap, new_hm};
+use crate::simplicial_complex::Step;
+use crate::{History, Vertex};
+
+// A chain over F_p, as a map from the sorted tuples of its faces to their nonzero coefficients
+type Terms<Point> = SCHashMap<Vec<Point>, u32>;
+
+impl<Point: Vertex> History<Point> {
+    // Lift a cycle of the simplified complex over F_p to a cycle of the original complex whose
+    // class is mapped to the class of `cycle` by the simplifications, which are homotopy
+    // equivalences. The steps are undone from the last one:
+    // - a relabeling by giving the vertices their former labels;
+    // - a contraction of `old` onto `new` by replacing `new` with `old` in the faces which were
+    //   only faces in that form, and adding a chain in the stars of `old` and `new` to make the
+    //   result a cycle again. The pinch test ensures that the union of these stars is
+    //   contractible, so such a chain exists, and it is mapped into the star of `new`, which is
+    //   a cone, so the class of the cycle is unchanged.
+    // - a nerve by subdividing barycentrically and mapping the barycenter of each face of the
+    //   nerve, i.e. of a set of intersecting facets, to a vertex of their intersection, which is
+    //   the homotopy equivalence of the nerve theorem.
+    pub fn lift(&self, cycle: &Chain<Point>, modulus: u32) -> Chain<Point> {
+        let mut chain: Terms<Point> = new_hm(cycle.len());
+        for (face, c) in cycle {
+            add_term(&mut chain, face.clone(), *c, modulus);
+        }
+
+        for step in self.steps.iter().rev() {
+            chain = match step {
+                Step::Relabeling(relabeling) => {
+                    let former: SCHashMap<Point, Point> =
+                        relabeling.iter().map(|(v, w)| (*w, *v)).collect();
+                    let mut lifted = new_hm(chain.len());
+                    for (face, c) in chain {
+                        add_term(
+                            &mut lifted,
+                            face.iter().map(|v| former[v]).collect(),
+                            c,
+                            modulus,
+                        );
+                    }
+
+                    lifted
+                }
+                Step::Contraction { old, new, star } => {
+                    let star = star.iter().map(|f| f.tuple()).collect_vec();
+                    lift_contraction(chain, *old, *new, &star, modulus)
+                }
+                Step::Nerve(facets) => {
+                    let facets = facets.iter().map(|f| f.tuple()).collect_vec();
+                    lift_nerve(chain, &facets, modulus)
+                }
+            };
+        }
+
+        chain.into_iter().sorted().collect()
+    }
+}
+
+fn lift_contraction<Point: Vertex>(
+    chain: Terms<Point>,
+    old: Point,
+    new: Point,
+    star: &[Vec<Point>],
+    m: u32,
+) -> Terms<Point> {
+    let is_face = |face: &[Point]| star.iter().any(|f| is_subtuple(face, f));
+    let mut lifted = new_hm(chain.len());
+    let mut k = 0;
+    for (face, c) in chain {
+        k = face.len();
+        if face.contains(&new) && !is_face(&face) {
+            let face = face
+                .iter()
+                .map(|v| if *v == new { old } else { *v })
+                .collect();
+            add_term(&mut lifted, face, c, m);
+        } else {
+            add_term(&mut lifted, face, c, m);
+        }
+    }
+    let defect = boundary(&lifted, m);
+    if defect.is_empty() {
+        return lifted;
+    }
+
+    // Solve for a chain `c` on the faces of the stars with `∂c = -defect`.
+    let faces = star
+        .iter()
+        .flat_map(|f| f.iter().copied().combinations(k))
+        .unique()
+        .collect_vec();
+    let mut rows: SCHashMap<Vec<Point>, usize> = new_hm(faces.len());
+    let mut column = |terms: Terms<Point>| {
+        let mut col = terms
+            .into_iter()
+            .map(|(f, c)| {
+                let n = rows.len();
+                (*rows.entry(f).or_insert(n), c)
+            })
+            .collect_vec();
+        col.sort_unstable();
+        SparseVector(col)
+    };
+    let mut reduction = Reduction::new(m);
+    for (j, face) in faces.iter().enumerate() {
+        let mut terms = new_hm(1);
+        add_term(&mut terms, face.clone(), 1, m);
+        let (col, combination) =
+            reduction.reduce(column(boundary(&terms, m)), SparseVector(vec![(j, 1)]));
+        reduction.push(col, combination);
+    }
+    let (rest, correction) = reduction.reduce(column(defect), SparseVector::default());
+    assert![
+        rest.pivot().is_none(),
+        "A cycle could not be lifted through a contraction of an edge."
+    ];
+    for (j, c) in correction.0 {
+        add_term(&mut lifted, faces[j].clone(), c, m);
+    }
+
+    lifted
+}
+
+fn lift_nerve<Point: Vertex>(chain: Terms<Point>, facets: &[Vec<Point>], m: u32) -> Terms<Point> {
+    let mut subdivisions: SCHashMap<Vec<Point>, Terms<Point>> = new_hm(0);
+    let mut lifted = new_hm(chain.len());
+    for (face, c) in chain {
+        for (f, d) in subdivide(&face, facets, &mut subdivisions, m) {
+            add_term(&mut lifted, f, mod_mul(c, d, m), m);
+        }
+    }
+
+    lifted
+}
+
+// The image of the barycentric subdivision of a face of the nerve. The subdivision of a face is
+// the cone from its barycenter over the subdivision of its boundary.
+fn subdivide<Point: Vertex>(
+    face: &[Point],
+    facets: &[Vec<Point>],
+    subdivisions: &mut SCHashMap<Vec<Point>, Terms<Point>>,
+    m: u32,
+) -> Terms<Point> {
+    if let Some(terms) = subdivisions.get(face) {
+        return terms.clone();
+    }
+
+    let mut intersection = facet(face[0], facets).to_vec();
+    for v in &face[1..] {
+        let other = facet(*v, facets);
+        intersection.retain(|w| other.binary_search(w).is_ok());
+    }
+    let barycenter = *intersection
+        .first()
+        .expect("The facets in a face of a nerve should intersect.");
+
+    let mut terms = new_hm(1);
+    if face.len() == 1 {
+        add_term(&mut terms, vec![barycenter], 1, m);
+    } else {
+        for i in 0..face.len() {
+            let mut boundary_face = face.to_vec();
+            boundary_face.remove(i);
+            let sign = if i % 2 == 0 { 1 } else { m - 1 };
+            for (f, c) in subdivide(&boundary_face, facets, subdivisions, m) {
+                let cone = std::iter::once(barycenter).chain(f).collect();
+                add_term(&mut terms, cone, mod_mul(c, sign, m), m);
+            }
+        }
+    }
+    subdivisions.insert(face.to_vec(), terms.clone());
+
+    terms
+}
+
+// The facet labeling a vertex of the nerve
+fn facet<Point: Vertex>(v: Point, facets: &[Vec<Point>]) -> &[Point] {
+    let i: usize = v
+        .try_into()
+        .ok()
+        .expect("A vertex of a nerve should label a facet.");
+
+    &facets[i]
+}
+
+fn boundary<Point: Vertex>(chain: &Terms<Point>, m: u32) -> Terms<Point> {
+    let mut terms = new_hm(chain.len());
+    for (face, c) in chain.iter().filter(|(f, _)| f.len() > 1) {
+        for i in 0..face.len() {
+            let mut boundary_face = face.clone();
+            boundary_face.remove(i);
+            let c = if i % 2 == 0 { *c } else { m - c };
+            add_term(&mut terms, boundary_face, c, m);
+        }
+    }
+
+    terms
+}
+
+// Add `c` times the face with the vertices of `face` in this order, which is the face with its
+// vertices sorted times the sign of the permutation sorting them, or vanishes if a vertex is
+// repeated.
+fn add_term<Point: Vertex>(chain: &mut Terms<Point>, mut face: Vec<Point>, c: u32, m: u32) {
+    let inversions = face
+        .iter()
+        .tuple_combinations()
+        .filter(|(a, b)| a > b)
+        .count();
+    face.sort_unstable();
+    if face.iter().tuple_windows().any(|(a, b)| a == b) {
+        return;
+    }
+    let c = if inversions % 2 == 0 { c } else { (m - c) % m };
+    match chain.entry(face) {
+        Entry::Occupied(entry) if (entry.get() + c) % m == 0 => {
+            entry.remove();
+        }
+        Entry::Occupied(mut entry) => *entry.get_mut() = (entry.get() + c) % m,
+        Entry::Vacant(entry) if c != 0 => {
+            entry.insert(c);
+        }
+        Entry::Vacant(_) => {}
+    }
+}
+
+// Whether the sorted tuple `f` is contained in the sorted tuple `g`
+fn is_subtuple<Point: Vertex>(f: &[Point], g: &[Point]) -> bool {
+    let mut rest = g.iter();
+
+    f.iter().all(|v| rest.any(|w| w == v))
+}
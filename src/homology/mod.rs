@@ -0,0 +1,124 @@
+// Homology over the prime fields F_p and the integers, computed by enumerating every face. These
+// computations are only practical for small complexes, such as the output of `sc-simplify` or the
+// intersections considered while accreting a subcomplex.
+
+mod arithmetic;
+mod chain_complex;
+mod cohomology;
+mod coinvariants;
+mod integral;
+mod lift;
+mod persistence;
+mod wedge;
+
+pub use arithmetic::is_prime;
+use chain_complex::ChainComplex;
+pub use cohomology::Cohomology;
+use coinvariants::CoinvariantComplex;
+pub use integral::AbelianGroup;
+pub use persistence::{Bar, FilteredComplex};
+pub use wedge::{Certificate, Wedge};
+
+use crate::{SimplicialComplex, SymmetricComplex, Vertex};
+
+// A chain over F_p, given by the faces on which it is nonzero, as sorted tuples, with their
+// coefficients
+pub type Chain<Point> = Vec<(Vec<Point>, u32)>;
+
+impl<Point: Vertex> SimplicialComplex<Point> {
+    // The Betti numbers over F_p, indexed by dimension
+    pub fn betti_numbers(&self, modulus: u32) -> Vec<usize> {
+        let mut betti = ChainComplex::unreduced(self, modulus).cell_betti_numbers();
+        betti.remove(0);
+
+        betti
+    }
+
+    // The reduced Betti numbers over F_p, indexed by dimension
+    pub fn reduced_betti_numbers(&self, modulus: u32) -> Vec<usize> {
+        let mut betti = ChainComplex::reduced(self, modulus).cell_betti_numbers();
+        betti.remove(0);
+
+        betti
+    }
+
+    // The Betti numbers over F_p of the pair `(self, sub)`, indexed by dimension. The subcomplex
+    // is assumed to be a subcomplex of `self`.
+    pub fn relative_betti_numbers(&self, sub: &Self, modulus: u32) -> Vec<usize> {
+        let mut betti = ChainComplex::relative(self, sub, modulus).cell_betti_numbers();
+        betti.remove(0);
+
+        betti
+    }
+
+    // The homology groups over the integers, indexed by dimension
+    pub fn integral_homology(&self) -> Vec<AbelianGroup> {
+        let mut groups = integral::homology(&ChainComplex::unreduced(self, 2));
+        groups.remove(0);
+
+        groups
+    }
+
+    // The reduced homology groups over the integers, indexed by dimension
+    pub fn reduced_integral_homology(&self) -> Vec<AbelianGroup> {
+        let mut groups = integral::homology(&ChainComplex::reduced(self, 2));
+        groups.remove(0);
+
+        groups
+    }
+
+    // Whether the complex is nonempty and has trivial reduced homology over F_p
+    pub fn is_acyclic(&self, modulus: u32) -> bool {
+        !self.is_empty()
+            && ChainComplex::reduced(self, modulus)
+                .cell_betti_numbers()
+                .iter()
+                .all(|b| *b == 0)
+    }
+
+    // Representative cycles of a basis of the homology over F_p, indexed by dimension
+    pub fn cycles(&self, modulus: u32) -> Vec<Vec<Chain<Point>>> {
+        representatives(&ChainComplex::unreduced(self, modulus))
+    }
+
+    // Representative cycles of a basis of the reduced homology over F_p, indexed by dimension. In
+    // dimension 0, these are differences of vertices in different components.
+    pub fn reduced_cycles(&self, modulus: u32) -> Vec<Vec<Chain<Point>>> {
+        representatives(&ChainComplex::reduced(self, modulus))
+    }
+
+    // The cohomology ring over F_2, with representative cocycles
+    pub fn cohomology(&self) -> Cohomology<Point> {
+        Cohomology::new(self)
+    }
+}
+
+impl SymmetricComplex {
+    // The Betti numbers over F_p of the chain complex of coinvariants, indexed by dimension. Its
+    // cells are the orbits of faces, so the faces are enumerated one orbit at a time. When p does
+    // not divide the order of the group, these are the dimensions of the invariant parts of the
+    // homology groups.
+    pub fn invariant_betti_numbers(&self, modulus: u32) -> Vec<usize> {
+        let mut betti = CoinvariantComplex::new(self, modulus, false).cell_betti_numbers();
+        betti.remove(0);
+
+        betti
+    }
+
+    // The same for reduced homology
+    pub fn reduced_invariant_betti_numbers(&self, modulus: u32) -> Vec<usize> {
+        let mut betti = CoinvariantComplex::new(self, modulus, true).cell_betti_numbers();
+        betti.remove(0);
+
+        betti
+    }
+}
+
+fn representatives<Point: Vertex>(cc: &ChainComplex<Point>) -> Vec<Vec<Chain<Point>>> {
+    cc.cycles()
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(k, cycles)| cycles.iter().map(|c| cc.chain(k, c)).collect())
+        .collect()
+}
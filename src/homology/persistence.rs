@@ -0,0 +1,224 @@
+use std::cmp::Ordering;
+
+use itertools::Itertools;
+
+use super::arithmetic::{SparseVector, reduce};
+use super::chain_complex::subtuple;
+
+use crate::helpers::{SCHashMap, SCHashSet, new_hm};
+use crate::{Face, SimplicialComplex, Vertex};
+
+// A filtered complex, in which each face enters at a time no earlier than its own faces. Every
+// face is listed, so this is meant for moderately sized complexes, which are first shrunk by
+// collapses which respect the filtration.
+#[derive(Clone, Debug, Default)]
+pub struct FilteredComplex<Point: Vertex> {
+    // The non-empty faces as sorted tuples, with their entry times
+    faces: SCHashMap<Vec<Point>, f64>,
+}
+
+// A bar of the barcode: a homology class of dimension `dimension` born at `birth`, which dies at
+// `death` or persists forever if `death` is infinite.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bar {
+    pub dimension: usize,
+    pub birth: f64,
+    pub death: f64,
+}
+
+impl<Point: Vertex> FilteredComplex<Point> {
+    // The filtration in which each face enters with the first facet containing it
+    pub fn from_facets<T>(facets: T) -> Self
+    where
+        T: IntoIterator<Item = (Face<Point>, f64)>,
+    {
+        let mut faces: SCHashMap<Vec<Point>, f64> = new_hm(0);
+        for (facet, time) in facets {
+            let tuple = facet.tuple();
+            assert![
+                tuple.len() < 64,
+                "Persistence can only be computed for complexes of dimension less than 63"
+            ];
+            for mask in 1..1u64 << tuple.len() {
+                let t = faces.entry(subtuple(&tuple, mask)).or_insert(time);
+                *t = t.min(time);
+            }
+        }
+
+        Self { faces }
+    }
+
+    // The filtration of `sc` in which each face enters at the given time. The time of a face
+    // should be no earlier than those of its faces, as for the greatest distance between its
+    // vertices in a Vietoris–Rips filtration.
+    pub fn from_times<F>(sc: &SimplicialComplex<Point>, time: F) -> Self
+    where
+        F: Fn(&[Point]) -> f64,
+    {
+        let mut faces: SCHashMap<Vec<Point>, f64> = new_hm(sc.len());
+        for facet in sc {
+            let tuple = facet.tuple();
+            assert![
+                tuple.len() < 64,
+                "Persistence can only be computed for complexes of dimension less than 63"
+            ];
+            for mask in 1..1u64 << tuple.len() {
+                faces
+                    .entry(subtuple(&tuple, mask))
+                    .or_insert_with_key(|f| time(f));
+            }
+        }
+
+        Self { faces }
+    }
+
+    // The number of non-empty faces
+    pub fn len(&self) -> usize {
+        self.faces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+
+    // The complex of the faces which remain, regardless of their entry times
+    pub fn complex(&self) -> SimplicialComplex<Point> {
+        let covered: SCHashSet<Vec<Point>> = self
+            .faces
+            .keys()
+            .flat_map(|f| boundary_faces(f).map(|(_, boundary)| boundary))
+            .collect();
+
+        SimplicialComplex::from_check_maximal(
+            self.faces
+                .keys()
+                .filter(|f| !covered.contains(*f))
+                .map(|f| Face::from_iter(f.iter().copied())),
+        )
+    }
+
+    // Remove free pairs of faces which enter at the same time. A face with a single coface of
+    // one more dimension is free in every complex of the filtration containing the pair, so each
+    // of these complexes either collapses or is left alone, and only bars of length zero are
+    // lost. Return the number of pairs removed.
+    pub fn collapse(&mut self) -> usize {
+        // The number of cofaces of one more dimension of each face, and the exclusive or of the
+        // vertices they add as `usize`, which is the added vertex when there is a single coface
+        let mut cofaces: SCHashMap<Vec<Point>, (usize, usize)> = new_hm(self.faces.len());
+        for face in self.faces.keys() {
+            cofaces.entry(face.clone()).or_default();
+            for (v, boundary) in boundary_faces(face) {
+                let entry = cofaces.entry(boundary).or_default();
+                entry.0 += 1;
+                entry.1 ^= v;
+            }
+        }
+
+        let mut queue = cofaces
+            .iter()
+            .filter(|(_, (count, _))| *count == 1)
+            .map(|(f, _)| f.clone())
+            .collect_vec();
+        let mut pairs = 0;
+        while let Some(face) = queue.pop() {
+            let Some(&(1, v)) = cofaces.get(&face) else {
+                continue;
+            };
+            let v = Point::try_from(v).ok().unwrap();
+            let mut coface = face.clone();
+            coface.insert(coface.partition_point(|w| *w < v), v);
+            if self.faces[&face] != self.faces[&coface] {
+                continue;
+            }
+
+            for f in [coface, face] {
+                self.faces.remove(&f);
+                cofaces.remove(&f);
+                for (w, boundary) in boundary_faces(&f) {
+                    if let Some(entry) = cofaces.get_mut(&boundary) {
+                        entry.0 -= 1;
+                        entry.1 ^= w;
+                        if entry.0 == 1 {
+                            queue.push(boundary);
+                        }
+                    }
+                }
+            }
+            pairs += 1;
+        }
+
+        pairs
+    }
+
+    // The bars of positive length over F_p, sorted by dimension and then by birth. The faces are
+    // ordered by entry time and then by dimension, which is a valid order for the filtration, and
+    // the boundary matrix is reduced from left to right. The pivots pair the faces which create
+    // classes with those which kill them, and the faces which create classes but are not paired
+    // give the bars which persist forever.
+    pub fn barcode(&self, modulus: u32) -> Vec<Bar> {
+        let order = self
+            .faces
+            .iter()
+            .sorted_by(|(f, s), (g, t)| {
+                s.partial_cmp(t)
+                    .unwrap_or(Ordering::Equal)
+                    .then(f.len().cmp(&g.len()))
+                    .then(f.cmp(g))
+            })
+            .collect_vec();
+        let index: SCHashMap<&[Point], usize> = order
+            .iter()
+            .enumerate()
+            .map(|(i, (f, _))| (f.as_slice(), i))
+            .collect();
+        let mut cols = order
+            .iter()
+            .map(|(f, _)| {
+                let mut col = (0..f.len())
+                    .filter(|_| f.len() > 1)
+                    .map(|i| {
+                        let mut boundary = f.to_vec();
+                        boundary.remove(i);
+                        let sign = if i % 2 == 0 { 1 } else { modulus - 1 };
+                        (index[boundary.as_slice()], sign % modulus)
+                    })
+                    .collect_vec();
+                col.sort_unstable();
+                SparseVector(col)
+            })
+            .collect_vec();
+        let pivots = reduce(&mut cols, modulus);
+
+        let mut bars = Vec::<Bar>::new();
+        for (i, (face, birth)) in order.iter().enumerate() {
+            if !cols[i].0.is_empty() {
+                continue;
+            }
+            let death = pivots.get(&i).map_or(f64::INFINITY, |j| *order[*j].1);
+            if death > **birth {
+                bars.push(Bar {
+                    dimension: face.len() - 1,
+                    birth: **birth,
+                    death,
+                });
+            }
+        }
+        bars.sort_by(|a, b| {
+            a.dimension
+                .cmp(&b.dimension)
+                .then(a.birth.partial_cmp(&b.birth).unwrap_or(Ordering::Equal))
+                .then(a.death.partial_cmp(&b.death).unwrap_or(Ordering::Equal))
+        });
+
+        bars
+    }
+}
+
+// The faces of codimension 1 of a sorted tuple, with the vertex each one leaves out as `usize`
+fn boundary_faces<Point: Vertex>(face: &[Point]) -> impl Iterator<Item = (usize, Vec<Point>)> + '_ {
+    (0..face.len()).filter(|_| face.len() > 1).map(|i| {
+        let mut boundary = face.to_vec();
+        boundary.remove(i);
+        (face[i].try_into().ok().unwrap(), boundary)
+    })
+}
@@ -0,0 +1,90 @@
+use super::FilteredComplex;
+
+use crate::{SimplicialComplex, Vertex};
+
+// A certified homotopy type: the complex is homotopy equivalent to a wedge of `count` spheres of
+// dimension `dimension`, where a wedge of no spheres is a point
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Wedge {
+    pub count: usize,
+    pub dimension: usize,
+    pub certificate: Certificate,
+}
+
+// The reason a complex is a wedge of spheres. Each applies to the complex left by collapses,
+// which has the same homotopy type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Certificate {
+    // The complex collapses to a vertex.
+    Collapsible,
+    // The complex collapses to a connected graph, which is a wedge of `edges - vertices + 1`
+    // circles.
+    Graph { vertices: usize, edges: usize },
+    // The complex collapses to the boundary of a simplex, which is a sphere.
+    SimplexBoundary,
+    // The presentation of the fundamental group has no generators after Tietze transformations,
+    // and the reduced integral homology is free and concentrated in a single dimension `d >= 2`,
+    // or trivial. By the Hurewicz theorem, a basis of the homology is represented by maps from
+    // `d`-spheres, whose wedge maps to the complex by an isomorphism in homology. Both are
+    // simply connected, so this is a homotopy equivalence by the Whitehead theorem.
+    SimplyConnected,
+}
+
+impl<Point: Vertex> SimplicialComplex<Point> {
+    // Try to certify that the complex is homotopy equivalent to a wedge of spheres of a single
+    // dimension. The complex is first shrunk by collapses. If that leaves a vertex, a graph or the
+    // boundary of a simplex, the homotopy type is read off directly; otherwise, it is recognized
+    // from the fundamental group and the integral homology. `None` means that no certificate was
+    // found, not that the complex is not such a wedge: the complex may be disconnected or have
+    // torsion in its homology, but the fundamental group may also be trivial without the Tietze
+    // transformations showing it. Every face is enumerated, so this is meant for small complexes,
+    // such as the output of `pinch`.
+    pub fn wedge_of_spheres(&self) -> Option<Wedge> {
+        if self.is_empty() || self.component_count() != 1 {
+            return None;
+        }
+        let mut filtration = FilteredComplex::from_facets(self.iter().map(|f| (f.clone(), 0.0)));
+        filtration.collapse();
+        let sc = filtration.complex();
+
+        let f_vector = sc.f_vector();
+        let wedge = |count, dimension, certificate| {
+            Some(Wedge {
+                count,
+                dimension,
+                certificate,
+            })
+        };
+        if f_vector == [1] {
+            return wedge(0, 0, Certificate::Collapsible);
+        }
+        if f_vector.len() == 2 {
+            let (vertices, edges) = (f_vector[0] as usize, f_vector[1] as usize);
+            // A connected graph has at least `vertices - 1` edges.
+            debug_assert![edges + 1 >= vertices];
+            return wedge(
+                edges + 1 - vertices,
+                1,
+                Certificate::Graph { vertices, edges },
+            );
+        }
+        let height = sc.height();
+        if sc.is_pure() && sc.len() == height + 1 && f_vector[0] as usize == height + 1 {
+            return wedge(1, height - 1, Certificate::SimplexBoundary);
+        }
+
+        if !sc.fundamental_group().is_trivial() {
+            return None;
+        }
+        let homology = sc.reduced_integral_homology();
+        if homology.iter().any(|h| !h.is_free()) {
+            return None;
+        }
+        let mut nontrivial = homology.iter().enumerate().filter(|(_, h)| h.rank > 0);
+        match (nontrivial.next(), nontrivial.next()) {
+            (None, _) => wedge(0, 0, Certificate::SimplyConnected),
+            (Some((d, h)), None) => wedge(h.rank, d, Certificate::SimplyConnected),
+            _ => None,
+        }
+    }
+}
@@ -1,12 +1,15 @@
-use std::io::{BufRead, stdin};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write, stdin, stdout};
+use std::path::Path;
 use std::time::Duration;
 
 use crate::Vertex;
 use crate::style::{info_sty_num, info_sty_str};
-use crate::{Face, SimplicialComplex};
+use crate::{Face, Reverse, SimplicialComplex, SymmetricComplex};
 
 use crate::ProgressBar;
 use indicatif::ProgressStyle;
+use itertools::Itertools;
 
 pub fn new_pb(n: usize) -> ProgressBar {
     let pb = ProgressBar::new(n as u64);
@@ -50,7 +53,7 @@ pub enum SC {
     Large(SimplicialComplex<u32>),
 }
 
-fn ambi_sc_info(asc: &SC, s: &str) {
+pub fn ambi_sc_info(asc: &SC, s: &str) {
     if let SC::Small(sc) = asc {
         sc_info(sc, s);
     } else if let SC::Large(sc) = asc {
@@ -58,54 +61,265 @@ fn ambi_sc_info(asc: &SC, s: &str) {
     }
 }
 
-// Refactor this to handle the check
-pub fn read_input(quiet: bool) -> SC {
-    let stdin = stdin();
-    let lines = stdin.lock().lines();
-    let mut max: u32 = 0;
-    let mut facets = Vec::<Face<u32>>::new();
-    for line in lines {
-        let facet = line
-            .expect("A complex should have at least one facet.")
-            .split(" ")
-            .filter(|v| !v.is_empty())
-            .map(|n| {
-                let m = n
-                    .parse()
-                    .expect("Pointices should be labeled by natural numbers less than 2^32.");
-                max = max.max(m);
-
-                m
-            })
-            .collect::<Face<u32>>();
-        if !facet.is_empty() {
-            facets.push(facet);
+// Parse a line of input as a facet.
+pub fn parse_facet(line: &str) -> Face<u32> {
+    line.split(" ")
+        .filter(|v| !v.is_empty())
+        .map(|n| {
+            n.parse()
+                .expect("Pointices should be labeled by natural numbers less than 2^32.")
+        })
+        .collect()
+}
+
+// Read a graph from a file, or from stdin if no file is given, as a list of edges with one edge
+// `v w` per line. A line with a single vertex adds an isolated vertex, which is returned as an
+// edge from the vertex to itself, as expected by `SimplicialComplex::clique_complex`.
+pub fn read_edges(path: Option<&Path>) -> Vec<(u32, u32)> {
+    input_lines(path)
+        .filter_map(|line| match parse_facet(&line).tuple()[..] {
+            [] => None,
+            [v] => Some((v, v)),
+            [v, w] => Some((v, w)),
+            _ => panic!["Each line of an edge list should hold one or two vertices."],
+        })
+        .collect()
+}
+
+// Read a table of numbers from a file, or from stdin if no file is given, such as a point cloud
+// with one point per line. The numbers may be separated by commas or spaces, as in CSV files, and
+// a header on the first line is skipped.
+pub fn read_table(path: Option<&Path>) -> Vec<Vec<f64>> {
+    let mut rows = Vec::<Vec<f64>>::new();
+    for (i, line) in input_lines(path).enumerate() {
+        let row: Result<Vec<f64>, _> = line
+            .split([',', ' ', '\t'])
+            .filter(|x| !x.is_empty())
+            .map(str::parse)
+            .collect();
+        match row {
+            Ok(row) if row.is_empty() => {}
+            Ok(row) => rows.push(row),
+            Err(_) if i == 0 => {}
+            Err(e) => panic!["Line {} of the table could not be parsed: {e}", i + 1],
         }
     }
 
-    max = max.max(
+    rows
+}
+
+// Read a symmetric matrix of distances between points, as for `read_table`.
+pub fn read_distance_matrix(path: Option<&Path>) -> Vec<Vec<f64>> {
+    let matrix = read_table(path);
+    let n = matrix.len();
+    assert![
+        matrix.iter().all(|row| row.len() == n),
+        "A distance matrix should have as many columns as rows."
+    ];
+    assert![
+        (0..n).all(|i| (0..i).all(|j| matrix[i][j] == matrix[j][i])),
+        "A distance matrix should be symmetric."
+    ];
+
+    matrix
+}
+
+// Read a filtered complex from a file, or from stdin if no file is given. Each line is a facet
+// followed by its entry time, as in `0 1 2 @ 0.5`, and a facet without a time enters at time 0.
+pub fn read_filtration(path: Option<&Path>) -> Vec<(Face<u32>, f64)> {
+    input_lines(path)
+        .filter_map(|line| {
+            let (facet, time) = line.split_once('@').unwrap_or((&line, "0"));
+            let facet = parse_facet(facet.trim());
+            let time = time.trim().parse().expect("Entry times should be numbers.");
+            (!facet.is_empty()).then_some((facet, time))
+        })
+        .collect()
+}
+
+// The greatest label which must fit in the vertex type, i.e. the greatest vertex label or the
+// number of facets, which may become vertex labels when taking nerves.
+pub fn max_label(facets: &[Face<u32>]) -> u32 {
+    facets.iter().flatten().copied().max().unwrap_or(0).max(
         facets
             .len()
             .try_into()
             .expect("There should be fewer than 2^32 facets"),
-    );
+    )
+}
+
+// Convert facets to a vertex type which holds their labels.
+pub fn convert_facets<Point: Vertex>(facets: Vec<Face<u32>>) -> Vec<Face<Point>> {
+    facets
+        .into_iter()
+        .map(|s| {
+            s.into_iter()
+                .map(|v| {
+                    (v as usize)
+                        .try_into()
+                        .ok()
+                        .expect("The vertex type is too small for the vertex labels.")
+                })
+                .collect::<Face<Point>>()
+        })
+        .collect()
+}
+
+// The lines of a file, or of stdin if no file is given
+fn input_lines(path: Option<&Path>) -> Box<dyn Iterator<Item = String>> {
+    let reader: Box<dyn BufRead> = match path {
+        Some(path) => Box::new(BufReader::new(
+            File::open(path).expect("The input file could not be opened."),
+        )),
+        None => Box::new(stdin().lock()),
+    };
+
+    Box::new(
+        reader
+            .lines()
+            .map(|l| l.expect("A complex should have at least one facet.")),
+    )
+}
+
+// Parse a line such as `(0 1 2)(3 4)` as a permutation of the vertices in cycle notation. The
+// permutation is given by the images of the vertices up to the greatest one it moves, and the
+// cycles are applied from left to right. Commas may separate the vertices, as in GAP.
+pub fn parse_permutation(line: &str) -> Vec<u32> {
+    let cycles: Vec<Vec<u32>> = line
+        .split(')')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(|c| {
+            c.strip_prefix('(')
+                .expect("A permutation should be a product of cycles such as `(0 1 2)(3 4)`.")
+                .split([' ', ','])
+                .filter(|v| !v.is_empty())
+                .map(|v| {
+                    v.parse()
+                        .expect("Vertices should be labeled by natural numbers less than 2^32.")
+                })
+                .collect()
+        })
+        .collect();
+
+    let len = cycles.iter().flatten().max().map_or(0, |v| *v as usize + 1);
+    let mut images: Vec<u32> = (0..len as u32).collect();
+    for cycle in cycles {
+        assert![
+            cycle.iter().all_unique(),
+            "A cycle should not repeat a vertex."
+        ];
+        let mut step: Vec<u32> = (0..len as u32).collect();
+        for (v, w) in cycle.iter().circular_tuple_windows() {
+            step[*v as usize] = *w;
+        }
+        images = images.into_iter().map(|v| step[v as usize]).collect();
+    }
+
+    images
+}
+
+// The permutation in cycle notation, as read by `parse_permutation`
+pub fn format_permutation(images: &[u32]) -> String {
+    let mut seen = vec![false; images.len()];
+    let mut cycles = String::new();
+    for start in 0..images.len() {
+        if seen[start] || images[start] as usize == start {
+            continue;
+        }
+        let mut cycle = vec![start as u32];
+        seen[start] = true;
+        let mut v = images[start] as usize;
+        while v != start {
+            cycle.push(v as u32);
+            seen[v] = true;
+            v = images[v] as usize;
+        }
+        cycles += &format!["({})", cycle.iter().join(" ")];
+    }
+
+    if cycles.is_empty() {
+        "()".to_string()
+    } else {
+        cycles
+    }
+}
+
+// Read the facets of the complexes in a file, or in stdin if no file is given, together with the
+// permutations of the vertices on lines in cycle notation. In the plain format, consecutive
+// complexes are separated by blank lines, as in the output of a pair. An XML file saved by
+// simpcomp holds a single complex.
+pub fn read_blocks_and_generators(path: Option<&Path>) -> (Vec<Vec<u32>>, Vec<Vec<Face<u32>>>) {
+    let mut lines = input_lines(path).peekable();
+    while lines.next_if(|l| l.trim().is_empty()).is_some() {}
+    if lines
+        .peek()
+        .is_some_and(|l| l.trim_start().starts_with('<'))
+    {
+        return (
+            Vec::new(),
+            vec![parse_xml(&lines.collect::<Vec<String>>().join("\n"))],
+        );
+    }
+
+    let mut generators = Vec::<Vec<u32>>::new();
+    let mut blocks = vec![Vec::<Face<u32>>::new()];
+    for line in lines {
+        if line.trim_start().starts_with('(') {
+            generators.push(parse_permutation(&line));
+            continue;
+        }
+        let facet = parse_facet(&line);
+        if !facet.is_empty() {
+            blocks.last_mut().unwrap().push(facet);
+        } else if !blocks.last().unwrap().is_empty() {
+            blocks.push(Vec::new());
+        }
+    }
+    if blocks.len() > 1 && blocks.last().unwrap().is_empty() {
+        blocks.pop();
+    }
+
+    (generators, blocks)
+}
 
-    let sc: SC = if facets.is_empty() {
+// Read the facets of the complexes in a file, or in stdin if no file is given. If permutations of
+// the vertices are given, each complex is the union of the orbits of its facets under the group
+// they generate.
+pub fn read_blocks(path: Option<&Path>) -> Vec<Vec<Face<u32>>> {
+    let (generators, blocks) = read_blocks_and_generators(path);
+
+    blocks
+        .into_iter()
+        .map(|b| expand_orbits(&generators, b))
+        .collect()
+}
+
+// The facets in the orbits of the given facets under the group generated by `generators`
+pub fn expand_orbits(generators: &[Vec<u32>], facets: Vec<Face<u32>>) -> Vec<Face<u32>> {
+    if generators.is_empty() {
+        return facets;
+    }
+
+    SymmetricComplex::new(generators.to_vec(), facets)
+        .facets()
+        .filter(|f| !f.is_empty())
+        .collect()
+}
+
+pub fn to_sc(facets: Vec<Face<u32>>) -> SC {
+    if facets.is_empty() {
         SC::Small(SimplicialComplex::<u16>::default())
-    } else if max > u16::MAX.into() {
+    } else if max_label(&facets) > u16::MAX.into() {
         SC::Large(SimplicialComplex::<u32>::from(facets))
     } else {
-        SC::Small(SimplicialComplex::<u16>::from(
-            facets
-                .into_iter()
-                .map(|s| {
-                    s.into_iter()
-                        .map(|v| v.try_into().unwrap())
-                        .collect::<Face<u16>>()
-                })
-                .collect::<Vec<Face<u16>>>(),
-        ))
-    };
+        SC::Small(SimplicialComplex::<u16>::from(convert_facets(facets)))
+    }
+}
+
+// Read a single complex from a file, or from stdin if no file is given. Blank lines are ignored.
+pub fn read_input(path: Option<&Path>, quiet: bool) -> SC {
+    let sc = to_sc(read_blocks(path).into_iter().flatten().collect());
 
     if !quiet {
         ambi_sc_info(&sc, "The original complex");
@@ -114,7 +328,80 @@ pub fn read_input(quiet: bool) -> SC {
     sc
 }
 
+// Parse the facets of a complex saved by simpcomp's `SCSaveXML`. Only complexes whose vertices
+// are labeled by natural numbers are supported.
+fn parse_xml(text: &str) -> Vec<Face<u32>> {
+    let facets = parse_gap_lists(
+        xml_property(text, "SCFacetsEx").expect("The XML file does not contain any facets."),
+    );
+    // The facets are given in terms of the indices of the vertices, starting from 1.
+    match xml_property(text, "SCVertices") {
+        Some(vertices) => {
+            let vertices: Vec<u32> = parse_gap_lists(&format!["[{vertices}]"])
+                .pop()
+                .unwrap_or_default();
+            facets
+                .into_iter()
+                .map(|f| {
+                    f.into_iter()
+                        .map(|i| {
+                            *(i as usize)
+                                .checked_sub(1)
+                                .and_then(|i| vertices.get(i))
+                                .expect("A facet contains a vertex which does not exist.")
+                        })
+                        .collect()
+                })
+                .collect()
+        }
+        None => facets.into_iter().map(Face::from_iter).collect(),
+    }
+}
+
+// The contents of the element `name`
+fn xml_property<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    let start = text.find(&format!["<{name}"])?;
+    let start = start + text[start..].find('>')? + 1;
+    let end = start + text[start..].find(&format!["</{name}>"])?;
+
+    Some(text[start..end].trim())
+}
+
+// Parse a GAP list of lists of natural numbers such as `[ [ 1, 2 ], [ 2, 3 ] ]`.
+fn parse_gap_lists(text: &str) -> Vec<Vec<u32>> {
+    let inner = text
+        .trim()
+        .strip_prefix('[')
+        .and_then(|t| t.strip_suffix(']'))
+        .expect("The XML file contains a malformed list.");
+
+    inner
+        .split('[')
+        .skip(1)
+        .map(|l| {
+            l.split(']')
+                .next()
+                .unwrap()
+                .split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(|v| {
+                    v.parse()
+                        .expect("Vertices should be labeled by natural numbers less than 2^32.")
+                })
+                .collect()
+        })
+        .collect()
+}
+
 pub fn write_sc<Point: Vertex>(sc: &SimplicialComplex<Point>) {
+    write_sc_to(sc, &mut stdout().lock()).expect("The complex could not be written to stdout.");
+}
+
+pub fn write_sc_to<Point: Vertex, W: Write>(
+    sc: &SimplicialComplex<Point>,
+    out: &mut W,
+) -> io::Result<()> {
     let mut facets_vec: Vec<Vec<Point>> = sc.into_iter().map(|s| s.to_vec()).collect();
     // Benchmark sorting unstable instead
     // `sort_by_key` should work here with Reverse, but the sad facet is that it doesn't. It
@@ -136,12 +423,100 @@ pub fn write_sc<Point: Vertex>(sc: &SimplicialComplex<Point>) {
     // The number of digits in the greatest vertex label
     let d = l.to_string().len();
     for f in facets_vec {
-        println![
+        writeln![
+            out,
             "{}",
             f.into_iter()
                 .map(|v| format!["{:>d$}", v])
                 .collect::<Vec<String>>()
                 .join(" "),
-        ];
+        ]?;
     }
+
+    Ok(())
+}
+
+// Write facets as they are generated, one per line, without collecting them first. Unlike
+// `write_sc_to`, this neither sorts the facets nor aligns the vertex labels.
+pub fn write_facets_to<Point: Vertex, W: Write>(
+    facets: impl IntoIterator<Item = Face<Point>>,
+    out: &mut W,
+) -> io::Result<()> {
+    for f in facets {
+        let mut vertices = f.to_vec();
+        vertices.sort_unstable_by_key(|v| Reverse(*v));
+        writeln![out, "{}", vertices.iter().join(" ")]?;
+    }
+
+    Ok(())
+}
+
+// Write the generators of a complex given up to symmetry in cycle notation, then one facet of each
+// orbit, in the format read by `read_blocks_and_generators`.
+pub fn write_symmetric_to<W: Write>(sc: &SymmetricComplex, out: &mut W) -> io::Result<()> {
+    for g in sc.generators() {
+        writeln![out, "{}", format_permutation(g)]?;
+    }
+
+    write_facets_to(
+        sc.representatives().iter().cloned().map(Face::from_iter),
+        out,
+    )
+}
+
+pub fn write_symmetric(sc: &SymmetricComplex) {
+    write_symmetric_to(sc, &mut stdout().lock())
+        .expect("The complex could not be written to stdout.");
+}
+
+pub fn write_facets<Point: Vertex>(facets: impl IntoIterator<Item = Face<Point>>) {
+    let mut out = BufWriter::new(stdout().lock());
+    write_facets_to(facets, &mut out)
+        .and_then(|_| out.flush())
+        .expect("The facets could not be written to stdout.");
+}
+
+// Write a complex in the XML format read by simpcomp's `SCLoadXML`.
+pub fn write_xml_to<Point: Vertex, W: Write>(
+    sc: &SimplicialComplex<Point>,
+    out: &mut W,
+) -> io::Result<()> {
+    let mut vertices: Vec<Point> = sc.iter().flatten().copied().collect();
+    vertices.sort_unstable();
+    vertices.dedup();
+    let mut facets: Vec<Vec<usize>> = sc
+        .iter()
+        .filter(|f| !f.is_empty())
+        .map(|f| {
+            f.tuple()
+                .iter()
+                .map(|v| vertices.binary_search(v).unwrap() + 1)
+                .collect()
+        })
+        .collect();
+    facets.sort_unstable();
+
+    let gap_list = |l: &[String]| format!["[ {} ]", l.join(", ")];
+    writeln![out, r#"<?xml version="1.0" encoding="UTF-8"?>"#]?;
+    writeln![out, r#"<SimplicialComplexV2 type="SCSimplicialComplex">"#]?;
+    writeln![
+        out,
+        r#"	<SCFacetsEx type="SCArray">{}</SCFacetsEx>"#,
+        gap_list(
+            &facets
+                .iter()
+                .map(|f| gap_list(&f.iter().map(|i| i.to_string()).collect::<Vec<_>>()))
+                .collect::<Vec<_>>()
+        )
+    ]?;
+    writeln![
+        out,
+        r#"	<SCVertices type="SCArray">{}</SCVertices>"#,
+        gap_list(&vertices.iter().map(|v| v.to_string()).collect::<Vec<_>>())
+    ]?;
+    writeln![out, "</SimplicialComplexV2>"]
+}
+
+pub fn write_xml<Point: Vertex>(sc: &SimplicialComplex<Point>) {
+    write_xml_to(sc, &mut stdout().lock()).expect("The complex could not be written to stdout.");
 }
@@ -20,12 +20,22 @@ mod helpers;
 mod partial_bijection_complex;
 mod simplicial_complex;
 mod style;
+mod symmetric_complex;
 
 // Public resources
+pub mod generators;
+pub mod homology;
 pub mod io;
-pub use partial_bijection_complex::partial_bijection_complex;
+pub mod progress_indicator;
+pub use partial_bijection_complex::{
+    partial_bijection_complex, partial_bijection_facets, partial_bijection_symmetric,
+};
 pub use simplicial_complex::Face;
+pub use simplicial_complex::History;
+pub use simplicial_complex::Presentation;
 pub use simplicial_complex::SimplicialComplex;
+pub use simplicial_complex::SubcomplexStrategy;
+pub use symmetric_complex::SymmetricComplex;
 
 pub trait Vertex:
     Default
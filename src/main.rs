@@ -1,15 +1,29 @@
+use std::collections::HashMap;
+use std::fs::{File, create_dir_all};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process;
 use std::sync::{
     Arc,
     atomic::{AtomicBool, Ordering},
 };
 
 use clap::Parser;
+use rayon::prelude::*;
 
-use sc_simplify::io::{SC, read_input, sc_info, write_sc};
-use sc_simplify::{SimplicialComplex, Vertex};
+use sc_simplify::io::{
+    SC, ambi_sc_info, expand_orbits, sc_info, to_sc, write_sc, write_sc_to, write_xml_to,
+};
+use sc_simplify::progress_indicator::{ProgressBars, ProgressObserver, Silent};
+use sc_simplify::{Face, SimplicialComplex, SymmetricComplex, Vertex};
 
 mod for_main;
-use for_main::{Cli, head_sty, info_sty_str};
+use for_main::{
+    AnyCheckpoint, Budget, Checkpoint, Cli, Command, Cut, Report, SharedArgs, SimplifyArgs, Stage,
+    Verification, cohomology, convert, fundamental_group, generate, head_sty, homology, info,
+    info_sty_str, persistence, read_checkpoint, read_shared, recognize, state_euler_characteristic,
+    usage_error, write_complex,
+};
 
 fn pair_write<Point: Vertex>(sc: &SimplicialComplex<Point>, bnd: &SimplicialComplex<Point>) {
     write_sc(sc);
@@ -17,105 +31,734 @@ fn pair_write<Point: Vertex>(sc: &SimplicialComplex<Point>, bnd: &SimplicialComp
     write_sc(bnd);
 }
 
-fn simplify<Point: Vertex>(mut sc: SimplicialComplex<Point>, cli: Cli) {
-    let interrupted = Arc::new(AtomicBool::new(false));
-    let intrpt = interrupted.clone();
+// Print a note about a stage which was cut short.
+fn report_cut(stage: Stage, cut: Option<Cut>, quiet: bool) {
+    if let Some(cut) = cut
+        && !quiet
+    {
+        eprintln![
+            "{}",
+            info_sty_str(format![
+                "{} was cut short because {}.",
+                stage.description(),
+                cut.explanation()
+            ])
+        ];
+    }
+}
 
-    ctrlc::set_handler(move || {
-        intrpt.store(true, Ordering::SeqCst);
-    })
-    .expect("Error setting Ctrl-C handler");
+// The stages of `simplify` for a single complex. Without a budget, as when several components are
+// simplified in parallel, every stage runs until the program is interrupted or a global limit is
+// reached.
+struct Context<'a> {
+    cli: &'a SimplifyArgs,
+    budget: Option<&'a Budget>,
+    interrupted: &'a Arc<AtomicBool>,
+    check_input: bool,
+    quiet: bool,
+    progress: &'a dyn ProgressObserver,
+}
 
-    let quiet = cli.quiet;
+impl Context<'_> {
+    fn is_interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::Relaxed)
+    }
 
-    if cli.skip_nerve || interrupted.load(Ordering::Relaxed) {
-        // Check if taking nerves is actually faster than checking this way
-        if cli.check_input {
-            sc.maximalify();
+    fn enter(&self, stage: Stage) -> Arc<AtomicBool> {
+        match self.budget {
+            Some(budget) => budget.enter(stage),
+            None => self.interrupted.clone(),
         }
+    }
+
+    fn leave(&self) -> Option<Cut> {
+        match self.budget {
+            Some(budget) => budget.leave(),
+            None => self.is_interrupted().then_some(Cut::Interrupted),
+        }
+    }
+}
+
+fn kind(acyclic: Option<u32>) -> &'static str {
+    if acyclic.is_some() {
+        "acyclic"
     } else {
-        // There is no need to perform checks if we reduce.
-        if !quiet {
-            eprintln!["\n{}", head_sty("Applying Čech nerves:")];
+        "contractible"
+    }
+}
+
+// Run the stages which have not been completed yet. Return the state of the run and whether the
+// complex and subcomplex form the output pair.
+fn run_stages<Point: Vertex>(
+    ctx: &Context,
+    checkpoint: Checkpoint<Point>,
+    report: &mut Report,
+    verification: &mut Verification,
+) -> (Checkpoint<Point>, bool) {
+    let quiet = ctx.quiet;
+    let Checkpoint {
+        stage,
+        pinch_loops,
+        acyclic,
+        complex: mut sc,
+        subcomplex: mut sub,
+    } = checkpoint;
+    // Once accretion has begun, we stick with the kind of subcomplex it started with.
+    let acyclic = if sub.is_some() {
+        acyclic
+    } else {
+        ctx.cli.acyclic
+    };
+    assert![
+        acyclic.is_none() || ctx.cli.verify.is_none() || acyclic == ctx.cli.verify,
+        "An acyclic subcomplex only preserves homology over its own field, so `--verify` should \
+         use the modulus of `--acyclic`."
+    ];
+    // The input can only be replaced by its nerve as the reference if the nerve stage runs.
+    if stage != Stage::Nerve || ctx.cli.skip_nerve || verification.wants(&sc) {
+        verification.record(None, &sc, sub.as_ref());
+    }
+    // The first stage which has not been completed. This is where a resumed run starts.
+    let mut next = stage;
+    // Whether `sc` and `sub` form the output pair rather than the facets of the complex which
+    // have not been accreted and the accreted subcomplex
+    let mut paired = stage == Stage::ReducePair;
+    // Every stage preserves the Euler characteristic of the state.
+    let euler = ctx
+        .cli
+        .verify_euler
+        .then(|| state_euler_characteristic(&sc, sub.as_ref()));
+    let verify_euler =
+        |stage: Stage, sc: &SimplicialComplex<Point>, sub: Option<&SimplicialComplex<Point>>| {
+            if let Some(expected) = euler {
+                let found = state_euler_characteristic(sc, sub);
+                assert![
+                    found == expected,
+                    "{} changed the Euler characteristic from {expected} to {found}.",
+                    stage.description()
+                ];
+            }
+        };
+
+    if next == Stage::Nerve {
+        if ctx.cli.skip_nerve || ctx.is_interrupted() {
+            // Check if taking nerves is actually faster than checking this way
+            if ctx.check_input {
+                sc.maximalify();
+            }
+        } else {
+            // There is no need to perform checks if we reduce.
+            if !quiet {
+                eprintln!["\n{}", head_sty("Applying Čech nerves:")];
+            }
+            report.begin();
+            let stop = ctx.enter(Stage::Nerve);
+            let nerve_count = sc.nerve_reduce(Some(&stop), ctx.progress);
+            let cut = ctx.leave();
+            report.end(Stage::Nerve, cut, &sc, None);
+            report.nerve_iterations(nerve_count);
+            if !quiet {
+                eprintln![];
+                if nerve_count > 0 {
+                    eprintln![];
+                    sc_info(&sc, "After reducing, the complex");
+                }
+            }
+            report_cut(Stage::Nerve, cut, quiet);
+            verify_euler(Stage::Nerve, &sc, None);
+            verification.record(Some(Stage::Nerve), &sc, None);
         }
-        let nerve_count = sc.nerve_reduce(quiet);
-        if !quiet {
-            eprintln![];
-            if nerve_count > 0 {
+        if !ctx.is_interrupted() {
+            next = Stage::Pinch;
+        }
+    }
+
+    let mut i = pinch_loops;
+    if next == Stage::Pinch {
+        if i > 0 && !ctx.is_interrupted() {
+            if !quiet {
+                eprintln!["\n{}", head_sty("Pinching edges:")];
+            }
+            report.begin();
+            let stop = ctx.enter(Stage::Pinch);
+            let mut pinched_edges = 0;
+            while i > 0 && !stop.load(Ordering::Relaxed) {
+                let n = sc.pinch(Some(&stop), ctx.progress);
+                if n == 0 {
+                    break;
+                }
+                pinched_edges += n;
+                i -= 1;
+            }
+            let cut = ctx.leave();
+            report.end(Stage::Pinch, cut, &sc, None);
+            report.pinched_edges(pinched_edges);
+            if !quiet {
                 eprintln![];
-                sc_info(&sc, "After reducing, the complex");
+                if i < pinch_loops {
+                    eprintln![];
+                    sc_info(&sc, "After pinching, the complex");
+                }
+            }
+            report_cut(Stage::Pinch, cut, quiet);
+            verify_euler(Stage::Pinch, &sc, None);
+        }
+        if !ctx.is_interrupted() {
+            next = Stage::Accretion;
+        }
+    }
+
+    let kind = kind(acyclic);
+    if next == Stage::Accretion
+        && !ctx.cli.no_pair
+        && !ctx.is_interrupted()
+        && (sc.height() > 0 || sub.is_some())
+    {
+        if !quiet {
+            eprintln!["\n{}", head_sty(format!["Accreting {kind} subcomplex:"])];
+        }
+
+        let strategy = ctx.cli.subcomplex_strategy();
+        report.begin();
+        let stop = ctx.enter(Stage::Accretion);
+        let contractible = match sub.take() {
+            Some(mut contractible) => {
+                sc.enlarge_subcomplex(
+                    &mut contractible,
+                    strategy,
+                    acyclic,
+                    Some(&stop),
+                    ctx.progress,
+                );
+                contractible
             }
+            None => match acyclic {
+                Some(p) => sc.acyclic_subcomplex(strategy, p, Some(&stop), ctx.progress),
+                None => sc.contractible_subcomplex(strategy, Some(&stop), ctx.progress),
+            },
+        };
+        let cut = ctx.leave();
+        report.end(Stage::Accretion, cut, &sc, Some(&contractible));
+
+        if !quiet {
+            eprintln!["\n"];
+            sc_info(&contractible, &format!["The {kind} subcomplex"]);
+        }
+        report_cut(Stage::Accretion, cut, quiet);
+        verify_euler(Stage::Accretion, &sc, Some(&contractible));
+        sub = Some(contractible);
+        if !ctx.is_interrupted() {
+            next = Stage::Minimize;
         }
     }
 
-    let mut i = cli.max_pinch_loops;
-    if i > 0 {
+    if next == Stage::Minimize
+        && !ctx.cli.skip_minimize_pair
+        && !ctx.is_interrupted()
+        && let Some(contractible) = sub.take()
+    {
         if !quiet {
-            eprintln!["\n{}", head_sty("Pinching edges:")];
+            eprintln!["\n{}", head_sty("Minimizing pair:")];
+        }
+        report.begin();
+        let stop = ctx.enter(Stage::Minimize);
+        let bnd = sc.minimize_pair(&contractible, Some(&stop), ctx.progress);
+        let cut = ctx.leave();
+        report.end(
+            Stage::Minimize,
+            cut,
+            &sc,
+            Some(bnd.as_ref().unwrap_or(&contractible)),
+        );
+        report_cut(Stage::Minimize, cut, quiet);
+
+        if let Some(mut bnd) = bnd {
+            // Accretion leaves no facets at all once it takes the whole complex, in which case
+            // the pair is trivial.
+            if sc.iter().next().is_none() {
+                sc = SimplicialComplex::default();
+                bnd = SimplicialComplex::default();
+            }
+            if !quiet {
+                eprintln!["\n"];
+                sc_info(&sc, "After minimizing, the complex");
+                sc_info(&bnd, "The subcomplex");
+            }
+            sub = Some(bnd);
+            paired = true;
+        } else if !ctx.is_interrupted() {
+            // Only this stage was cut short, so we carry on with the unminimized pair.
+            sc = SimplicialComplex::<Point>::from_iter(
+                sc.into_iter().chain(contractible.iter().cloned()),
+            );
+            sub = Some(contractible);
+            paired = true;
+        } else {
+            sub = Some(contractible);
         }
-        while i > 0
-            && !interrupted.load(Ordering::Relaxed)
-            && sc.pinch(Some(interrupted.clone()), quiet)
-        {
-            i -= 1;
+        verify_euler(Stage::Minimize, &sc, sub.as_ref());
+        if !ctx.is_interrupted() {
+            next = Stage::ReducePair;
         }
+    }
+
+    if next == Stage::ReducePair
+        && ctx.cli.iterate_pair
+        && !ctx.is_interrupted()
+        && let Some(bnd) = &mut sub
+    {
+        if !quiet {
+            eprintln!["\n{}", head_sty("Reducing pair:")];
+        }
+        report.begin();
+        let stop = ctx.enter(Stage::ReducePair);
+        let round_count = sc.reduce_pair(bnd, acyclic, Some(&stop), ctx.progress);
+        let cut = ctx.leave();
+        report.end(Stage::ReducePair, cut, &sc, Some(bnd));
         if !quiet {
             eprintln![];
-            if i < cli.max_pinch_loops {
+            if round_count > 0 {
                 eprintln![];
-                sc_info(&sc, "After pinching, the complex");
+                sc_info(&sc, "After reducing, the complex");
+                sc_info(bnd, "The subcomplex");
             }
         }
+        report_cut(Stage::ReducePair, cut, quiet);
+        verify_euler(Stage::ReducePair, &sc, Some(bnd));
     }
 
-    if cli.no_pair || interrupted.load(Ordering::Relaxed) {
-        write_sc(&sc);
-    } else {
-        if sc.height() > 0 {
-            if !quiet {
-                eprintln!["\n{}", head_sty("Accreting contractible subcomplex:")];
+    (
+        Checkpoint {
+            stage: next,
+            pinch_loops: i,
+            acyclic,
+            complex: sc,
+            subcomplex: sub,
+        },
+        paired,
+    )
+}
+
+// The complexes to print once the stages have run: a complex and, unless only one complex is
+// printed, a subcomplex. The empty complex has no output pair.
+#[allow(clippy::type_complexity)]
+fn output<Point: Vertex>(
+    checkpoint: Checkpoint<Point>,
+    paired: bool,
+    no_pair: bool,
+    interrupted: bool,
+) -> Option<(SimplicialComplex<Point>, Option<SimplicialComplex<Point>>)> {
+    let Checkpoint {
+        complex: sc,
+        subcomplex: sub,
+        ..
+    } = checkpoint;
+    match sub {
+        Some(bnd) if paired => Some((sc, Some(bnd))),
+        // If accretion or minimization was interrupted, we skip minimizing the pair.
+        Some(contractible) => Some((
+            SimplicialComplex::<Point>::from_iter(
+                sc.into_iter().chain(contractible.iter().cloned()),
+            ),
+            Some(contractible),
+        )),
+        None if no_pair || interrupted => Some((sc, None)),
+        None => None,
+    }
+}
+
+fn simplify<Point: Vertex>(
+    checkpoint: Checkpoint<Point>,
+    cli: &SimplifyArgs,
+    shared: &SharedArgs,
+    interrupted: Arc<AtomicBool>,
+) {
+    let budget = Budget::start(cli.global_limits(), cli.stage_limits(), interrupted.clone());
+    let is_interrupted = || interrupted.load(Ordering::Relaxed);
+    let quiet = shared.quiet;
+    let progress: &dyn ProgressObserver = if quiet { &Silent } else { &ProgressBars::new() };
+
+    assert![
+        !(cli.no_pair && checkpoint.subcomplex.is_some()),
+        "A run which was interrupted after accretion began must print a pair."
+    ];
+    assert![
+        cli.no_pair || !shared.xml,
+        "Pairs cannot be printed in the XML format, so `--xml` requires `--no-pair`."
+    ];
+    let mut report = Report::new(cli.report.is_some(), &checkpoint.complex);
+    let ctx = Context {
+        cli,
+        budget: Some(&budget),
+        interrupted: &interrupted,
+        check_input: shared.check_input,
+        quiet,
+        progress,
+    };
+    let mut verification = Verification::new(cli.verify);
+    let (checkpoint, paired) = run_stages(&ctx, checkpoint, &mut report, &mut verification);
+
+    if let Some(path) = &cli.report
+        && let Err(e) = report.write(path, is_interrupted())
+    {
+        eprintln![
+            "{}",
+            info_sty_str(format!["The report could not be saved: {e}."])
+        ];
+    }
+
+    if is_interrupted()
+        && let Some(path) = &cli.checkpoint
+    {
+        match checkpoint.write(path) {
+            Ok(()) if !quiet => eprintln![
+                "{}",
+                info_sty_str(format!["Saved a checkpoint to {}.", path.display()])
+            ],
+            Ok(()) => {}
+            Err(e) => eprintln![
+                "{}",
+                info_sty_str(format!["The checkpoint could not be saved: {e}."])
+            ],
+        }
+    }
+
+    let kind = kind(checkpoint.acyclic);
+    let Some((sc, sub)) = output(checkpoint, paired, cli.no_pair, is_interrupted()) else {
+        if !quiet {
+            eprintln![
+                "{}",
+                info_sty_str(format!["The empty complex contains no {kind} subcomplex."])
+            ];
+        }
+        return;
+    };
+    match &sub {
+        Some(bnd) => pair_write(&sc, bnd),
+        None => write_complex(&sc, shared.xml),
+    }
+    report_verification(verification.check(&sc, sub.as_ref()).into_iter(), quiet);
+}
+
+// Print the outcomes of `--verify` and exit with an error if the homology of any output differs
+// from that of its input.
+fn report_verification(outcomes: impl Iterator<Item = (bool, String)>, quiet: bool) {
+    let mut agrees = true;
+    for (ok, description) in outcomes {
+        if !ok {
+            agrees = false;
+            eprintln!["{}", info_sty_str(description)];
+        } else if !quiet {
+            eprintln!["{}", info_sty_str(description)];
+        }
+    }
+    if !agrees {
+        process::exit(1);
+    }
+}
+
+// Write the output for a component to its own file.
+fn write_component<Point: Vertex>(
+    path: &Path,
+    sc: &SimplicialComplex<Point>,
+    sub: Option<&SimplicialComplex<Point>>,
+    xml: bool,
+) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    match sub {
+        Some(sub) => {
+            write_sc_to(sc, &mut out)?;
+            writeln![out]?;
+            write_sc_to(sub, &mut out)?;
+        }
+        None if xml => write_xml_to(sc, &mut out)?,
+        None => write_sc_to(sc, &mut out)?,
+    }
+
+    out.flush()
+}
+
+// The outputs for the components as a single pair, whose vertices are relabeled so that the
+// components stay disjoint. The homotopy type of a pair X, A is that of X with a cone on A
+// attached, which would join every component meeting A. So only the first component with a
+// subcomplex keeps it, and each of the others is replaced with X_i with a cone on A_i attached,
+// whose apex is a new vertex. The merged pair then has the homotopy type of the input.
+#[allow(clippy::type_complexity)]
+fn merge<Point: Vertex>(
+    outputs: &[(SimplicialComplex<Point>, Option<SimplicialComplex<Point>>)],
+) -> (SimplicialComplex<u32>, Option<SimplicialComplex<u32>>) {
+    let mut offset = 0;
+    let mut complex = Vec::<Face<u32>>::new();
+    let mut subcomplex = None::<Vec<Face<u32>>>;
+    let mut coned = false;
+    for (sc, sub) in outputs {
+        let mut vertices: Vec<Point> = sc
+            .iter()
+            .chain(sub.iter().flatten())
+            .flatten()
+            .copied()
+            .collect();
+        vertices.sort_unstable();
+        vertices.dedup();
+        let relabel = |f: &Face<Point>| -> Face<u32> {
+            f.iter()
+                .map(|v| offset + vertices.binary_search(v).unwrap() as u32)
+                .collect()
+        };
+        complex.extend(sc.iter().filter(|f| !f.is_empty()).map(relabel));
+        match sub {
+            Some(sub) if subcomplex.is_none() => {
+                subcomplex = Some(sub.iter().filter(|f| !f.is_empty()).map(relabel).collect());
             }
+            Some(sub) => {
+                // The cone on an empty subcomplex is its apex.
+                let apex = offset + vertices.len() as u32;
+                complex.extend(sub.iter().map(|f| {
+                    let mut cone = relabel(f);
+                    cone.insert(apex);
+                    cone
+                }));
+                coned = true;
+                offset += 1;
+            }
+            None => {}
+        }
+        offset += vertices.len() as u32;
+    }
+    let to_complex = |facets: Vec<Face<u32>>| {
+        if facets.is_empty() {
+            SimplicialComplex::default()
+        } else {
+            SimplicialComplex::from_check_sorted(facets)
+        }
+    };
+    // A facet of X_i may lie in a facet of A_i, and thus in the cone on it.
+    let complex = if coned {
+        SimplicialComplex::from_check_maximal(complex)
+    } else {
+        to_complex(complex)
+    };
 
-            let contractible = sc.contractible_subcomplex(quiet);
+    (complex, subcomplex.map(to_complex))
+}
 
+// Simplify each connected component separately, in parallel.
+fn simplify_components<Point: Vertex>(
+    sc: SimplicialComplex<Point>,
+    cli: &SimplifyArgs,
+    shared: &SharedArgs,
+    interrupted: Arc<AtomicBool>,
+) {
+    let components = sc.components();
+    if components.is_empty() {
+        return simplify(
+            Checkpoint::start(sc, cli.max_pinch_loops, cli.acyclic),
+            cli,
+            shared,
+            interrupted,
+        );
+    }
+    drop(sc);
+    assert![
+        cli.no_pair || !shared.xml || cli.component_output.is_some(),
+        "Pairs cannot be printed in the XML format, so `--xml` requires `--no-pair`."
+    ];
+
+    // Stages run concurrently in different components, so the budget only watches the global
+    // limits.
+    let _budget = Budget::start(cli.global_limits(), HashMap::new(), interrupted.clone());
+    let quiet = shared.quiet;
+    let progress: &dyn ProgressObserver = if quiet { &Silent } else { &ProgressBars::new() };
+    if !quiet {
+        eprintln![
+            "\n{}",
+            head_sty(format!["Simplifying {} components:", components.len()])
+        ];
+    }
+    // The components report their progress together, as the number of components finished.
+    let ctx = Context {
+        cli,
+        budget: None,
+        interrupted: &interrupted,
+        check_input: shared.check_input,
+        quiet: true,
+        progress: &Silent,
+    };
+    progress.stage_started("Simplified components", Some(components.len()));
+    let outputs: Vec<_> = components
+        .into_par_iter()
+        .map(|component| {
+            let mut report = Report::new(false, &component);
+            let mut verification = Verification::new(cli.verify);
+            let (checkpoint, paired) = run_stages(
+                &ctx,
+                Checkpoint::start(component, cli.max_pinch_loops, cli.acyclic),
+                &mut report,
+                &mut verification,
+            );
+            let (sc, sub) = output(checkpoint, paired, cli.no_pair, ctx.is_interrupted())
+                .expect("A non-empty complex should have an output.");
+            let outcome = verification.check(&sc, sub.as_ref());
+            progress.items_processed(1);
+
+            (sc, sub, outcome)
+        })
+        .collect();
+    progress.stage_finished();
+    let (outputs, outcomes): (Vec<_>, Vec<_>) = outputs
+        .into_iter()
+        .map(|(sc, sub, outcome)| ((sc, sub), outcome))
+        .unzip();
+    if !quiet {
+        eprintln![];
+        if ctx.is_interrupted() {
+            eprintln![
+                "{}",
+                info_sty_str("Simplifying the components was cut short.")
+            ];
+        }
+    }
+
+    match &cli.component_output {
+        Some(dir) => {
+            create_dir_all(dir).expect("The directory for the components could not be created.");
+            let extension = if shared.xml && cli.no_pair {
+                "xml"
+            } else {
+                "sc"
+            };
+            for (i, (sc, sub)) in outputs.iter().enumerate() {
+                write_component(
+                    &dir.join(format!["component-{i}.{extension}"]),
+                    sc,
+                    sub.as_ref(),
+                    shared.xml,
+                )
+                .expect("The output for a component could not be written.");
+            }
             if !quiet {
-                eprintln!["\n"];
-                if sc.height() > 0 {
-                    sc_info(&contractible, "The contractible subcomplex");
-                } else {
-                    eprintln![
-                        "{}",
-                        info_sty_str("The empty complex contains no contractible subcomplex.")
-                    ];
+                eprintln![
+                    "{}",
+                    info_sty_str(format![
+                        "Wrote the outputs for {} components to {}.",
+                        outputs.len(),
+                        dir.display()
+                    ])
+                ];
+            }
+        }
+        None => {
+            let (sc, sub) = merge(&outputs);
+            if !quiet {
+                sc_info(&sc, "The merged complex");
+                if let Some(sub) = &sub {
+                    sc_info(sub, "The merged subcomplex");
                 }
             }
+            match sub {
+                Some(sub) => pair_write(&sc, &sub),
+                None => write_complex(&sc, shared.xml),
+            }
+        }
+    }
+    report_verification(
+        outcomes.into_iter().enumerate().filter_map(|(i, outcome)| {
+            outcome.map(|(ok, description)| (ok, format!["Component {i}: {description}"]))
+        }),
+        quiet,
+    );
+}
 
-            if cli.skip_minimize_pair {
-                sc = SimplicialComplex::<Point>::from_iter(
-                    sc.into_iter().chain(contractible.iter().cloned()),
-                );
-                pair_write(&sc, &contractible);
-            } else {
-                if !quiet {
-                    eprintln!["\n{}", head_sty("Minimizing pair:")];
-                }
-                let bnd = sc.minimize_pair(contractible);
-                if !quiet {
-                    eprintln!["\n"];
-                    sc_info(&sc, "After minimizing, the complex");
-                    sc_info(&bnd, "The subcomplex");
-                }
-                pair_write(&sc, &bnd);
+// Read the input complex. A complex given by orbits of facets is first reduced with Čech nerves
+// which respect the group action, unless the nerve stage is skipped, and then its orbits are
+// expanded, since the later stages work with every facet.
+fn read_simplify_input(cli: &SimplifyArgs, shared: &SharedArgs, intrpt: &AtomicBool) -> SC {
+    let (generators, blocks) = read_shared(shared);
+    let facets = blocks.into_iter().flatten().collect();
+    let facets = if generators.is_empty() || cli.skip_nerve {
+        expand_orbits(&generators, facets)
+    } else {
+        let mut sc = SymmetricComplex::new(generators, facets);
+        let progress: &dyn ProgressObserver = if shared.quiet {
+            &Silent
+        } else {
+            eprintln![
+                "{}",
+                head_sty("Applying Čech nerves to the orbits of facets:")
+            ];
+            &ProgressBars::new()
+        };
+        sc.nerve_reduce(Some(intrpt), progress);
+        if !shared.quiet {
+            eprintln![];
+        }
+        sc.facets().filter(|f| !f.is_empty()).collect()
+    };
+    let sc = to_sc(facets);
+    if !shared.quiet {
+        ambi_sc_info(&sc, "The original complex");
+    }
+
+    sc
+}
+
+fn run_simplify(cli: &SimplifyArgs, shared: &SharedArgs) {
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let intrpt = interrupted.clone();
+    ctrlc::set_handler(move || {
+        intrpt.store(true, Ordering::SeqCst);
+    })
+    .expect("Error setting Ctrl-C handler");
+
+    if cli.components {
+        match read_simplify_input(cli, shared, &interrupted) {
+            SC::Small(sc) => simplify_components(sc, cli, shared, interrupted),
+            SC::Large(sc) => simplify_components(sc, cli, shared, interrupted),
+        }
+        return;
+    }
+
+    let checkpoint = match &cli.resume {
+        Some(path) => {
+            let checkpoint = read_checkpoint(path, shared.quiet);
+            let conflict = match &checkpoint {
+                AnyCheckpoint::Small(c) => c.conflict(cli.no_pair, cli.verify),
+                AnyCheckpoint::Large(c) => c.conflict(cli.no_pair, cli.verify),
+            };
+            if let Some(message) = conflict {
+                usage_error(message);
             }
+            checkpoint
         }
+        None => match read_simplify_input(cli, shared, &interrupted) {
+            SC::Small(sc) => {
+                AnyCheckpoint::Small(Checkpoint::start(sc, cli.max_pinch_loops, cli.acyclic))
+            }
+            SC::Large(sc) => {
+                AnyCheckpoint::Large(Checkpoint::start(sc, cli.max_pinch_loops, cli.acyclic))
+            }
+        },
+    };
+    match checkpoint {
+        AnyCheckpoint::Small(c) => simplify(c, cli, shared, interrupted),
+        AnyCheckpoint::Large(c) => simplify(c, cli, shared, interrupted),
     }
 }
 
 fn main() {
-    let cli = Cli::parse();
+    let cli = Cli::parse_args();
+    let shared = &cli.shared;
 
-    match read_input(cli.quiet) {
-        SC::Small(sc) => simplify(sc, cli),
-        SC::Large(sc) => simplify(sc, cli),
+    match &cli.command {
+        None => run_simplify(&cli.simplify, shared),
+        Some(Command::Simplify(args)) => run_simplify(args, shared),
+        Some(Command::Info) => info(shared),
+        Some(Command::Convert) => convert(shared),
+        Some(Command::Homology(args)) => homology(args, shared),
+        Some(Command::Cohomology(args)) => cohomology(args, shared),
+        Some(Command::FundamentalGroup(args)) => fundamental_group(args, shared),
+        Some(Command::Persistence(args)) => persistence(args, shared),
+        Some(Command::Recognize(args)) => recognize(args, shared),
+        Some(Command::Generate(generator)) => generate(generator, shared),
     }
 }
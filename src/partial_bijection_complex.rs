@@ -1,140 +1,152 @@
-// Benchmark replacing usize with u8
 use std::cmp::min;
 
 use itertools::Itertools;
 
-// use crate::Rc;
-use crate::Reverse;
-use crate::helpers::{SCHashMap, SCHashSet, new_hs, new_vec};
 use crate::max;
-use crate::{Face, SimplicialComplex};
-use std::rc::Rc;
+use crate::{Face, SimplicialComplex, SymmetricComplex};
+
+// The complex of partial bijections between sets with a and b elements: the order complex of the
+// non-empty partial bijections ordered by inclusion. When a = b, the partial bijections missing a
+// single pair are left out.
+//
+// With m = min(a, b) and n = max(a, b), a partial bijection is an injection from a subset of 0..m,
+// its domain, to 0..n. Each maximal chain is given by an injection from 0..m and the order in which
+// its pairs are removed, so the facets can be generated one at a time, without listing the
+// vertices or the facets first.
+pub fn partial_bijection_facets(a: u8, b: u8) -> impl Iterator<Item = Face<u32>> {
+    let (m, n) = (min(a, b), max(a, b));
+    let ranking = Ranking::new(m, n);
+
+    (0..n)
+        .permutations(m.into())
+        .cartesian_product((0..m).permutations(m.into()))
+        // When a = b, removing the first two pairs in either order gives the same facet, as the
+        // partial bijection in between is left out.
+        .filter(move |(_, order)| !(m == n && m >= 2 && order[0] > order[1]))
+        .map(move |(values, order)| {
+            (0..m as usize)
+                .filter(|t| !(m == n && *t == 1))
+                .map(|t| {
+                    let domain = (0..m).filter(|i| !order[..t].contains(i)).collect_vec();
+                    let image = domain.iter().map(|i| values[*i as usize]).collect_vec();
+                    ranking.rank(&domain, &image)
+                })
+                .collect()
+        })
+}
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-struct PartialBijection(Vec<(u8, u8)>);
+pub fn partial_bijection_complex(a: u8, b: u8) -> SimplicialComplex<u32> {
+    SimplicialComplex::from_check_sorted(partial_bijection_facets(a, b))
+}
 
-impl PartialBijection {
-    fn len(&self) -> usize {
-        self.0.len()
+// The same complex, as the orbit of a single facet under S_m × S_n acting on the partial
+// bijections by permuting the elements of both sets. Each symmetric group is generated by a
+// transposition and a cycle.
+pub fn partial_bijection_symmetric(a: u8, b: u8) -> SymmetricComplex {
+    let (m, n) = (min(a, b), max(a, b));
+    let ranking = Ranking::new(m, n);
+    // The partial bijections, as sorted lists of pairs
+    let vertices = (1..=m)
+        .filter(|k| !(m == n && k + 1 == m))
+        .flat_map(|k| {
+            (0..m).combinations(k.into()).flat_map(move |domain| {
+                (0..n)
+                    .permutations(k.into())
+                    .map(move |image| domain.iter().copied().zip(image).collect_vec())
+            })
+        })
+        .sorted_by_key(|pairs| ranking.rank_pairs(pairs))
+        .collect_vec();
+    let generator = |f: &dyn Fn(u8, u8) -> (u8, u8)| -> Vec<u32> {
+        vertices
+            .iter()
+            .map(|pairs| ranking.rank_pairs(&pairs.iter().map(|(i, j)| f(*i, *j)).collect_vec()))
+            .collect()
+    };
+    let mut generators = Vec::new();
+    if m >= 2 {
+        generators.push(generator(&|i, j| (transposition(i), j)));
+        generators.push(generator(&|i, j| ((i + 1) % m, j)));
+    }
+    if n >= 2 {
+        generators.push(generator(&|i, j| (i, transposition(j))));
+        generators.push(generator(&|i, j| (i, (j + 1) % n)));
     }
+    let facets = partial_bijection_facets(a, b).take(1);
 
-    fn reduce(&self, i: usize) -> Self {
-        let mut reduced = self.clone();
-        reduced.0.remove(i);
+    SymmetricComplex::new(generators, facets)
+}
 
-        reduced
+fn transposition(i: u8) -> u8 {
+    match i {
+        0 => 1,
+        1 => 0,
+        _ => i,
     }
 }
 
-struct FacetGenerator(Vec<(u8, usize)>);
+// Vertex ids are assigned by ranking partial bijections: first by their size, then by their
+// domain, then by the values they take.
+struct Ranking {
+    n: u8,
+    // The first id of the partial bijections of each size
+    offsets: Vec<u64>,
+}
 
-impl FacetGenerator {
-    fn len(&self) -> usize {
-        self.0.len()
-    }
+impl Ranking {
+    fn new(m: u8, n: u8) -> Self {
+        let mut offsets = vec![0u64];
+        for k in 1..=m {
+            let count = if m == n && k + 1 == m {
+                0
+            } else {
+                binomial(m, k) * arrangements(n, k)
+            };
+            offsets.push(offsets.last().unwrap() + count);
+        }
+        assert![
+            *offsets.last().unwrap() <= u32::MAX as u64 + 1,
+            "The complex of partial bijections should have at most 2^32 vertices."
+        ];
 
-    fn vals(&self) -> Vec<u8> {
-        self.0.iter().map(|(b, _)| *b).collect()
+        Self { n, offsets }
     }
 
-    fn perm(&self) -> Vec<usize> {
-        let mut perm: Vec<usize> = self.0.iter().map(|(_, i)| *i).collect();
-
-        perm.truncate(perm.len() - 1);
-        for i in 0..perm.len() - 1 {
-            for j in i + 1..perm.len() {
-                if perm[i] < perm[j] {
-                    perm[j] -= 1;
-                }
-            }
-        }
-
-        perm
+    // The id of the partial bijection taking the values `image` on the sorted `domain`
+    fn rank(&self, domain: &[u8], image: &[u8]) -> u32 {
+        let k = domain.len() as u8;
+        // The rank of the domain among the subsets with k elements, in colexicographic order
+        let domain_rank: u64 = domain
+            .iter()
+            .enumerate()
+            .map(|(i, d)| binomial(*d, i as u8 + 1))
+            .sum();
+        // The rank of the values among the injections, in lexicographic order
+        let image_rank = image.iter().enumerate().fold(0u64, |r, (j, v)| {
+            let smaller = (0..*v).filter(|w| !image[..j].contains(w)).count() as u64;
+            r * (self.n - j as u8) as u64 + smaller
+        });
+
+        (self.offsets[k as usize - 1] + domain_rank * arrangements(self.n, k) + image_rank) as u32
     }
 
-    fn generate(&self, n: u8, check_len: bool) -> Vec<PartialBijection> {
-        let m = self.len();
-        let mut facet_vec = new_vec::<PartialBijection>(m);
-        let mut vert = PartialBijection((0..m as u8).zip(self.vals()).collect());
-
-        facet_vec.push(vert.clone());
-        for i in self.perm() {
-            vert = vert.reduce(i);
-            if !(check_len && m as u8 == n && vert.len() == m - 1) {
-                facet_vec.push(vert.clone());
-            }
-        }
+    // The id of the partial bijection given by its pairs, in any order
+    fn rank_pairs(&self, pairs: &[(u8, u8)]) -> u32 {
+        let (domain, image): (Vec<u8>, Vec<u8>) = pairs.iter().copied().sorted().unzip();
 
-        facet_vec
+        self.rank(&domain, &image)
     }
 }
 
-impl From<(Vec<u8>, Vec<usize>)> for FacetGenerator {
-    fn from(v: (Vec<u8>, Vec<usize>)) -> Self {
-        Self(v.0.iter().copied().zip(v.1.iter().copied()).collect())
+fn binomial(n: u8, k: u8) -> u64 {
+    if k > n {
+        return 0;
     }
-}
 
-pub fn partial_bijection_complex(a: u8, b: u8) -> SimplicialComplex<u32> {
-    let (m, n) = (min(a, b), max(a, b));
+    (0..k as u64).fold(1, |c, i| c * (n as u64 - i) / (i + 1))
+}
 
-    let facet_gens = (0..n)
-        .permutations(m.into())
-        .cartesian_product((0..m as usize).permutations(m.into()))
-        .map(|g| g.into());
-
-    let mut verts_vec: Vec<PartialBijection> = facet_gens
-        .clone()
-        .fold(
-            new_hs::<PartialBijection>(n.into()),
-            |mut set, fg: FacetGenerator| {
-                set.extend(fg.generate(n, false));
-
-                set
-            },
-        )
-        .into_iter()
-        .collect();
-    verts_vec.sort_by_key(|bj| Reverse(bj.len()));
-
-    let mut sorted_vec = new_vec::<Rc<PartialBijection>>(verts_vec.len());
-    let mut sorted_set = new_hs::<Rc<PartialBijection>>(verts_vec.len());
-    let mut i = 0;
-
-    // Inefficient but who cares
-    while !verts_vec.is_empty() {
-        let vert = &verts_vec[i];
-        let sub_verts_set: SCHashSet<Rc<PartialBijection>> = if vert.len() > 1 {
-            (0..vert.len()).map(|j| Rc::new(vert.reduce(j))).collect()
-        } else {
-            new_hs(0)
-        };
-        if sub_verts_set.is_subset(&sorted_set) {
-            let vert = Rc::new(verts_vec.remove(i));
-            if !(m == n && vert.len() == (m - 1).into()) {
-                sorted_vec.push(vert.clone());
-            }
-            sorted_set.insert(vert);
-            i = 0;
-        } else {
-            i += 1;
-        }
-    }
-    drop(sorted_set);
-
-    let vert_dict: SCHashMap<Rc<PartialBijection>, u32> =
-        sorted_vec.into_iter().zip(0u32..).collect();
-    let facets = facet_gens
-        .map(|fg| Face::<u32>::from_iter(fg.generate(n, true).into_iter().map(|v| vert_dict[&v])));
-
-    if m == n {
-        SimplicialComplex::from(
-            facets
-                .collect::<SCHashSet<Face<u32>>>()
-                .into_iter()
-                .collect::<Vec<Face<u32>>>(),
-        )
-    } else {
-        SimplicialComplex::from(facets.collect::<Vec<Face<u32>>>())
-    }
+// The number of injections from a set with k elements to one with n elements
+fn arrangements(n: u8, k: u8) -> u64 {
+    (0..k as u64).map(|i| n as u64 - i).product()
 }
@@ -0,0 +1,70 @@
+use std::sync::Mutex;
+
+use crate::ProgressBar;
+use crate::io::{new_pb, new_spnr};
+use crate::style::upd_sty;
+
+// Receives progress reports from long-running methods of `SimplicialComplex`. A method starts at
+// most one stage at a time, and every stage it starts is finished before it returns.
+pub trait ProgressObserver: Sync {
+    // A stage with `total` items has started. The total is `None` if it is not known in advance.
+    fn stage_started(&self, name: &str, total: Option<usize>);
+
+    // `count` more items of the current stage have been processed.
+    fn items_processed(&self, count: usize);
+
+    // The current stage has something to say about its state, e.g. "Pinched 12 edges".
+    fn status(&self, _status: &str) {}
+
+    fn stage_finished(&self);
+}
+
+// Ignores all progress reports
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Silent;
+
+impl ProgressObserver for Silent {
+    fn stage_started(&self, _name: &str, _total: Option<usize>) {}
+
+    fn items_processed(&self, _count: usize) {}
+
+    fn stage_finished(&self) {}
+}
+
+// Draws a progress bar, or a spinner if the number of items is unknown, on stderr for each stage
+#[derive(Debug, Default)]
+pub struct ProgressBars {
+    bar: Mutex<Option<ProgressBar>>,
+}
+
+impl ProgressBars {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ProgressObserver for ProgressBars {
+    fn stage_started(&self, name: &str, total: Option<usize>) {
+        let bar = total.map_or_else(new_spnr, new_pb);
+        bar.set_message(upd_sty(name));
+        *self.bar.lock().unwrap() = Some(bar);
+    }
+
+    fn items_processed(&self, count: usize) {
+        if let Some(bar) = self.bar.lock().unwrap().as_ref() {
+            bar.inc(count as u64);
+        }
+    }
+
+    fn status(&self, status: &str) {
+        if let Some(bar) = self.bar.lock().unwrap().as_ref() {
+            bar.set_message(upd_sty(status));
+        }
+    }
+
+    fn stage_finished(&self) {
+        if let Some(bar) = self.bar.lock().unwrap().take() {
+            bar.finish();
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::BTreeSet;
+
+use itertools::Itertools;
+use rayon::prelude::*;
+
+use crate::helpers::{SCHashMap, SCHashSet, new_hm, new_hs};
+use crate::{SimplicialComplex, Vertex};
+
+impl<Point: Vertex> SimplicialComplex<Point> {
+    // The clique (flag) complex of a graph given by its edges, whose facets are the maximal
+    // cliques of the graph. An edge from a vertex to itself only adds the vertex, so that isolated
+    // vertices can be given.
+    pub fn clique_complex<T>(edges: T) -> Self
+    where
+        T: IntoIterator<Item = (Point, Point)>,
+    {
+        let mut index: SCHashMap<Point, u32> = new_hm(0);
+        let mut vertices = Vec::<Point>::new();
+        let mut neighbors = Vec::<SCHashSet<u32>>::new();
+        for (v, w) in edges {
+            let [i, j] = [v, w].map(|u| {
+                *index.entry(u).or_insert_with(|| {
+                    vertices.push(u);
+                    neighbors.push(new_hs(0));
+                    (vertices.len() - 1)
+                        .try_into()
+                        .expect("A graph should have fewer than 2^32 vertices.")
+                })
+            });
+            if i != j {
+                neighbors[i as usize].insert(j);
+                neighbors[j as usize].insert(i);
+            }
+        }
+
+        // The maximal cliques are distinct, so there is no need to check the facets.
+        Self::from_check_sorted(
+            maximal_cliques(&neighbors)
+                .into_iter()
+                .map(|c| c.into_iter().map(|i| vertices[i as usize]).collect()),
+        )
+    }
+}
+
+// The maximal cliques of the graph on 0..n with the given lists of neighbors, found by the
+// Bron–Kerbosch algorithm with pivoting. As suggested by Eppstein, Löffler and Strash, the vertices
+// are taken in a degeneracy order, and the cliques whose first vertex in this order is v are
+// searched for among the later neighbors of v. These searches are independent, so they run in
+// parallel.
+pub(crate) fn maximal_cliques(neighbors: &[SCHashSet<u32>]) -> Vec<Vec<u32>> {
+    if neighbors.is_empty() {
+        return vec![Vec::new()];
+    }
+    let order = degeneracy_order(neighbors);
+    let mut position = vec![0; neighbors.len()];
+    for (i, v) in order.iter().enumerate() {
+        position[*v as usize] = i;
+    }
+
+    order
+        .par_iter()
+        .flat_map_iter(|v| {
+            let (later, earlier): (Vec<u32>, Vec<u32>) = neighbors[*v as usize]
+                .iter()
+                .partition(|w| position[**w as usize] > position[*v as usize]);
+            let mut cliques = Vec::new();
+            bron_kerbosch(neighbors, &mut vec![*v], later, earlier, &mut cliques);
+
+            cliques
+        })
+        .collect()
+}
+
+// The vertices in the order in which they are removed when a vertex of least degree is removed
+// each time. Each vertex has at most d later neighbors, where d is the degeneracy of the graph.
+fn degeneracy_order(neighbors: &[SCHashSet<u32>]) -> Vec<u32> {
+    let mut degree = neighbors.iter().map(|a| a.len()).collect_vec();
+    let mut queue: BTreeSet<(usize, u32)> = (0..neighbors.len() as u32)
+        .map(|v| (degree[v as usize], v))
+        .collect();
+    let mut order = Vec::with_capacity(neighbors.len());
+    while let Some((_, v)) = queue.pop_first() {
+        order.push(v);
+        for w in &neighbors[v as usize] {
+            let w = *w as usize;
+            if queue.remove(&(degree[w], w as u32)) {
+                degree[w] -= 1;
+                queue.insert((degree[w], w as u32));
+            }
+        }
+    }
+
+    order
+}
+
+// Add the maximal cliques containing the clique `clique` to `cliques`. They are extended by the
+// vertices of `candidates`, and those containing a vertex of `excluded` have already been found.
+// Only the candidates not adjacent to a pivot need to be tried, as any maximal clique contains the
+// pivot or one of them.
+fn bron_kerbosch(
+    neighbors: &[SCHashSet<u32>],
+    clique: &mut Vec<u32>,
+    mut candidates: Vec<u32>,
+    mut excluded: Vec<u32>,
+    cliques: &mut Vec<Vec<u32>>,
+) {
+    let Some(pivot) = candidates.iter().chain(&excluded).copied().max_by_key(|u| {
+        candidates
+            .iter()
+            .filter(|v| neighbors[*u as usize].contains(v))
+            .count()
+    }) else {
+        cliques.push(clique.clone());
+        return;
+    };
+
+    let tried = candidates
+        .iter()
+        .copied()
+        .filter(|v| !neighbors[pivot as usize].contains(v))
+        .collect_vec();
+    for v in tried {
+        let adjacent = &neighbors[v as usize];
+        clique.push(v);
+        bron_kerbosch(
+            neighbors,
+            clique,
+            candidates
+                .iter()
+                .copied()
+                .filter(|w| adjacent.contains(w))
+                .collect(),
+            excluded
+                .iter()
+                .copied()
+                .filter(|w| adjacent.contains(w))
+                .collect(),
+            cliques,
+        );
+        clique.pop();
+        candidates.retain(|w| *w != v);
+        excluded.push(v);
+    }
+}
@@ -0,0 +1,208 @@
+use itertools::Itertools;
+
+use super::SimplicialComplex;
+use crate::Vertex;
+use crate::helpers::{SCHashMap, SCHashSet, new_hm, new_hs, new_vd};
+
+// A presentation of a group by the generators `x1, ..., xn` and relations, which are words in
+// the generators. The letter `k` of a word stands for `xk` and `-k` for its inverse.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Presentation {
+    pub generator_count: usize,
+    pub relations: Vec<Vec<i32>>,
+}
+
+impl<Point: Vertex> SimplicialComplex<Point> {
+    // A presentation of the fundamental group of the component of the least vertex by the edge-path
+    // group. The edges of a spanning tree of the 1-skeleton are trivial, the other edges
+    // generate the group, and each 2-face gives a relation. The presentation is then simplified
+    // with Tietze transformations. Every 2-face is listed, so this is meant for small complexes,
+    // such as the output of `pinch`.
+    pub fn fundamental_group(&self) -> Presentation {
+        let mut neighbors: SCHashMap<Point, Vec<Point>> = new_hm(0);
+        for (v, lower) in self.edge_table() {
+            for w in lower {
+                neighbors.entry(v).or_default().push(w);
+                neighbors.entry(w).or_default().push(v);
+            }
+        }
+        let Some(base) = self.iter().flatten().min().copied() else {
+            return Presentation::default();
+        };
+
+        // A breadth-first spanning tree of the component of `base`
+        let mut tree: SCHashSet<(Point, Point)> = new_hs(neighbors.len());
+        let mut component: SCHashSet<Point> = new_hs(neighbors.len());
+        let mut queue = new_vd::<Point>(neighbors.len());
+        component.insert(base);
+        queue.push_back(base);
+        while let Some(v) = queue.pop_front() {
+            for w in neighbors.get(&v).into_iter().flatten() {
+                if component.insert(*w) {
+                    tree.insert((v.min(*w), v.max(*w)));
+                    queue.push_back(*w);
+                }
+            }
+        }
+
+        // The edges outside the tree, numbered from 1
+        let generators: SCHashMap<(Point, Point), i32> = component
+            .iter()
+            .flat_map(|v| {
+                neighbors
+                    .get(v)
+                    .into_iter()
+                    .flatten()
+                    .filter(move |w| v < *w)
+                    .map(move |w| (*v, *w))
+            })
+            .filter(|e| !tree.contains(e))
+            .sorted_unstable()
+            .zip(1..)
+            .collect();
+        let letter = |v: Point, w: Point| {
+            if v < w {
+                generators.get(&(v, w)).copied()
+            } else {
+                generators.get(&(w, v)).map(|k| -k)
+            }
+        };
+
+        let relations = self
+            .iter()
+            .filter(|f| f.len() >= 3 && f.iter().any(|v| component.contains(v)))
+            .flat_map(|f| f.tuple().into_iter().combinations(3))
+            .collect::<SCHashSet<_>>()
+            .into_iter()
+            .map(|t| {
+                [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])]
+                    .into_iter()
+                    .filter_map(|(v, w)| letter(v, w))
+                    .collect()
+            })
+            .collect();
+
+        let mut presentation = Presentation {
+            generator_count: generators.len(),
+            relations,
+        };
+        presentation.simplify();
+
+        presentation
+    }
+}
+
+impl Presentation {
+    // Whether the group is trivial, which is only detected if the simplified presentation has no
+    // generators
+    pub fn is_trivial(&self) -> bool {
+        self.generator_count == 0
+    }
+
+    // Simplify with Tietze transformations: the relations are reduced freely and cyclically, and
+    // duplicate or trivial ones are removed. A generator which appears exactly once in a relation
+    // is expressed in terms of the others and eliminated, starting from the shortest relations.
+    pub fn simplify(&mut self) {
+        let mut eliminated = vec![false; self.generator_count + 1];
+        loop {
+            for r in &mut self.relations {
+                reduce(r);
+            }
+            self.relations.retain(|r| !r.is_empty());
+            for r in &mut self.relations {
+                *r = canonical(r);
+            }
+            self.relations
+                .sort_unstable_by(|r, s| r.len().cmp(&s.len()).then(r.cmp(s)));
+            self.relations.dedup();
+
+            let Some((i, position)) = self.relations.iter().enumerate().find_map(|(i, r)| {
+                r.iter()
+                    .position(|a| r.iter().filter(|b| b.abs() == a.abs()).count() == 1)
+                    .map(|position| (i, position))
+            }) else {
+                break;
+            };
+            // Rotate the relation to `x^e w`, so that `x = w^-1` if `e = 1` and `x = w` otherwise.
+            let mut r = self.relations.remove(i);
+            r.rotate_left(position);
+            let x = r[0];
+            let mut w = r.split_off(1);
+            if x > 0 {
+                w = inverse(&w);
+            }
+            eliminated[x.unsigned_abs() as usize] = true;
+            for r in &mut self.relations {
+                *r = r
+                    .iter()
+                    .flat_map(|a| match a {
+                        a if *a == x.abs() => w.clone(),
+                        a if *a == -x.abs() => inverse(&w),
+                        a => vec![*a],
+                    })
+                    .collect();
+            }
+        }
+
+        // Number the remaining generators from 1.
+        let labels: Vec<i32> = eliminated
+            .iter()
+            .scan(0, |n, e| {
+                if !*e {
+                    *n += 1;
+                }
+                Some(*n - 1)
+            })
+            .collect();
+        self.generator_count = eliminated.iter().skip(1).filter(|e| !**e).count();
+        for r in &mut self.relations {
+            for a in r.iter_mut() {
+                *a = a.signum() * labels[a.unsigned_abs() as usize];
+            }
+        }
+        for r in &mut self.relations {
+            *r = canonical(r);
+        }
+        self.relations
+            .sort_unstable_by(|r, s| r.len().cmp(&s.len()).then(r.cmp(s)));
+    }
+}
+
+fn inverse(w: &[i32]) -> Vec<i32> {
+    w.iter().rev().map(|a| -a).collect()
+}
+
+// Cancel adjacent inverse letters, and then the first and last letters while they are inverse.
+fn reduce(w: &mut Vec<i32>) {
+    let mut reduced = Vec::with_capacity(w.len());
+    for a in w.iter() {
+        if reduced.last() == Some(&-a) {
+            reduced.pop();
+        } else {
+            reduced.push(*a);
+        }
+    }
+    let mut start = 0;
+    while reduced.len() >= start + 2 && reduced[start] == -reduced[reduced.len() - 1] {
+        start += 1;
+        reduced.pop();
+    }
+    *w = reduced.split_off(start);
+}
+
+// The least of the rotations of a word and its inverse, which define the same relation, where
+// the letters are ordered as `x1 < x1^-1 < x2 < ...`
+fn canonical(w: &[i32]) -> Vec<i32> {
+    let inverse = inverse(w);
+
+    (0..w.len())
+        .flat_map(|i| {
+            let mut u = w.to_vec();
+            let mut v = inverse.clone();
+            u.rotate_left(i);
+            v.rotate_left(i);
+            [u, v]
+        })
+        .min_by_key(|u| u.iter().map(|a| (a.abs(), *a < 0)).collect_vec())
+        .unwrap_or_default()
+}
@@ -0,0 +1,61 @@
+use std::sync::atomic::AtomicBool;
+
+use crate::helpers::SCHashMap;
+use crate::progress_indicator::ProgressObserver;
+use crate::{Face, SimplicialComplex, Vertex};
+
+// A record of the simplifications applied to a complex, from which the cycles of the simplified
+// complex can be lifted to cycles of the original complex, e.g. with `History::lift`
+#[derive(Clone, Debug)]
+pub struct History<Point: Vertex> {
+    pub(crate) steps: Vec<Step<Point>>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum Step<Point: Vertex> {
+    // The complex with these facets was replaced with its nerve, whose vertex `i` is the facet `i`.
+    Nerve(Vec<Face<Point>>),
+    // The vertex `old` was identified with `new`, and `star` lists the facets which contained
+    // either of them beforehand.
+    Contraction {
+        old: Point,
+        new: Point,
+        star: Vec<Face<Point>>,
+    },
+    // Each vertex `v` was relabeled `relabeling[v]`.
+    Relabeling(SCHashMap<Point, Point>),
+}
+
+impl<Point: Vertex> Default for History<Point> {
+    fn default() -> Self {
+        Self { steps: Vec::new() }
+    }
+}
+
+impl<Point: Vertex> History<Point> {
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+impl<Point: Vertex> SimplicialComplex<Point> {
+    // Reduce with nerves as `nerve_reduce` does, and record the nerves in `history`
+    pub fn nerve_reduce_recorded(
+        &mut self,
+        history: &mut History<Point>,
+        intrpt: Option<&AtomicBool>,
+        progress: &dyn ProgressObserver,
+    ) -> usize {
+        self.nerve_reduce_recording(intrpt, Some(history), progress)
+    }
+
+    // Pinch as `pinch` does, and record the contracted edges and the relabeling in `history`
+    pub fn pinch_recorded(
+        &mut self,
+        history: &mut History<Point>,
+        intrpt: Option<&AtomicBool>,
+        progress: &dyn ProgressObserver,
+    ) -> usize {
+        self.pinch_relative(None, intrpt, Some(history), progress)
+    }
+}
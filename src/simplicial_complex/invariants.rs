@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+use std::mem;
+
+use num::Integer;
+
+use crate::Vertex;
+use crate::helpers::{SCHashMap, SCHashSet, new_hm, new_hs};
+use crate::{Face, Reverse, SimplicialComplex};
+
+// Invariants computed from the facets alone. None of them enumerates the faces of the complex, so
+// they are practical for complexes whose faces would not fit in memory.
+impl<Point: Vertex> SimplicialComplex<Point> {
+    // The dimension of the complex, which is -1 for the empty complex
+    pub fn dimension(&self) -> isize {
+        self.iter().map(|f| f.len()).max().unwrap_or(0) as isize - 1
+    }
+
+    // Whether all facets have the same dimension
+    pub fn is_pure(&self) -> bool {
+        let mut sizes = self.iter().map(|f| f.len()).filter(|l| *l > 0);
+        let first = sizes.next();
+
+        sizes.all(|l| Some(l) == first)
+    }
+
+    // The numbers of faces of each dimension, starting from the vertices. The empty face is not
+    // counted, so the f-vector of the empty complex is empty.
+    pub fn f_vector(&self) -> Vec<u128> {
+        let mut facets: Vec<Vec<Point>> = self
+            .iter()
+            .filter(|f| !f.is_empty())
+            .map(|f| f.tuple())
+            .collect();
+        // Counting the large facets first keeps the overlaps with earlier facets small.
+        facets.sort_unstable_by_key(|f| Reverse(f.len()));
+
+        count_faces(&facets)
+    }
+
+    // The alternating sum of the f-vector
+    pub fn euler_characteristic(&self) -> i128 {
+        self.f_vector()
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                if i % 2 == 0 {
+                    *f as i128
+                } else {
+                    -(*f as i128)
+                }
+            })
+            .sum()
+    }
+
+    // The number of connected components
+    pub fn component_count(&self) -> usize {
+        self.vertex_classes()
+            .iter()
+            .filter(|(v, root)| v == root)
+            .count()
+    }
+
+    // The connected components, ordered by their first facets. The empty complex has none.
+    pub fn components(&self) -> Vec<Self> {
+        let mut classes = self.vertex_classes();
+        let mut index: SCHashMap<Point, usize> = new_hm(self.len());
+        let mut components = Vec::<Vec<Face<Point>>>::new();
+        for facet in self.iter().filter(|f| !f.is_empty()) {
+            let root = find(&mut classes, *facet.iter().next().unwrap());
+            let i = *index.entry(root).or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[i].push(facet.clone());
+        }
+
+        components.into_iter().map(Self::from_iter).collect()
+    }
+
+    // The vertices grouped into connected components by merging the vertices of each facet. Every
+    // vertex points towards the representative of its component.
+    fn vertex_classes(&self) -> SCHashMap<Point, Point> {
+        let mut parent: SCHashMap<Point, Point> = new_hm(self.len());
+        for facet in self {
+            let mut vertices = facet.iter();
+            let Some(first) = vertices.next() else {
+                continue;
+            };
+            let root = find(&mut parent, *first);
+            for v in vertices {
+                let other = find(&mut parent, *v);
+                parent.insert(other, root);
+            }
+        }
+
+        parent
+    }
+
+    // The number of vertices of each degree in the 1-skeleton, keyed by degree
+    pub fn degree_distribution(&self) -> BTreeMap<usize, usize> {
+        let mut neighbors: SCHashMap<Point, SCHashSet<Point>> = new_hm(self.len());
+        for facet in self {
+            for v in facet {
+                neighbors
+                    .entry(*v)
+                    .or_insert_with(|| new_hs(facet.len()))
+                    .extend(facet.iter().filter(|w| *w != v));
+            }
+        }
+
+        let mut distribution = BTreeMap::new();
+        for adj in neighbors.values() {
+            *distribution.entry(adj.len()).or_default() += 1;
+        }
+
+        distribution
+    }
+}
+
+// The representative of the class of `v`, which becomes a class of its own if it is new
+fn find<Point: Vertex>(parent: &mut SCHashMap<Point, Point>, v: Point) -> Point {
+    let mut root = v;
+    while let Some(p) = parent.get(&root).copied()
+        && p != root
+    {
+        root = p;
+    }
+    parent.insert(root, root);
+    // Compress the path so that later searches are quick.
+    let mut w = v;
+    while w != root {
+        w = parent.insert(w, root).unwrap();
+    }
+
+    root
+}
+
+// Count the faces of the union of the given simplices by inclusion-exclusion. If K_i is the
+// intersection of the i-th simplex F_i with the union of the earlier ones, the faces of F_i which
+// are new are those not in K_i, so f(F_1 ∪ ... ∪ F_n) = Σ f(F_i) - f(K_i). The complex K_i is the
+// union of the simplices F_i ∩ F_j for j < i, so its faces are counted recursively.
+fn count_faces<Point: Vertex>(facets: &[Vec<Point>]) -> Vec<u128> {
+    let mut f = Vec::<u128>::new();
+    // The indices of the facets already counted which contain each vertex
+    let mut star: SCHashMap<Point, Vec<usize>> = new_hm(facets.len());
+    // The intersection of the current facet with each earlier facet meeting it, as the set of
+    // positions in the current facet of the shared vertices
+    let mut overlaps = vec![0u128; facets.len()];
+    let mut met = Vec::<usize>::new();
+
+    for (i, facet) in facets.iter().enumerate() {
+        assert![
+            facet.len() <= 128,
+            "Facets should have at most 128 vertices to count their faces."
+        ];
+        for (p, v) in facet.iter().enumerate() {
+            for &j in star.get(v).into_iter().flatten() {
+                if overlaps[j] == 0 {
+                    met.push(j);
+                }
+                overlaps[j] |= 1 << p;
+            }
+        }
+        // Many earlier facets meet the current one in the same face, so these are deduplicated
+        // before they are sorted.
+        let masks: SCHashSet<u128> = met.drain(..).map(|j| mem::take(&mut overlaps[j])).collect();
+        let masks = masks.into_iter().collect();
+        add_new_faces(&mut f, facet.len(), &count_mask_faces(&maximal(masks)));
+
+        for v in facet {
+            star.entry(*v).or_default().push(i);
+        }
+    }
+
+    f
+}
+
+// Count the faces of the union of simplices given as sets of positions in a facet, in the same way
+fn count_mask_faces(masks: &[u128]) -> Vec<u128> {
+    let mut f = Vec::<u128>::new();
+    for (i, m) in masks.iter().enumerate() {
+        let overlaps = maximal(masks[..i].iter().map(|n| m & n).collect());
+        add_new_faces(
+            &mut f,
+            m.count_ones() as usize,
+            &count_mask_faces(&overlaps),
+        );
+    }
+
+    f
+}
+
+// Add the faces of a simplex with `size` vertices to `f`, except the `old` ones it shares with
+// the simplices already counted.
+fn add_new_faces(f: &mut Vec<u128>, size: usize, old: &[u128]) {
+    if f.len() < size {
+        f.resize(size, 0);
+    }
+    // The number of faces with k vertices is (size choose k). Dividing by the common factor first
+    // keeps the intermediate products within the final values.
+    let mut c: u128 = 1;
+    for k in 1..=size {
+        let g = c.gcd(&(k as u128));
+        c = (c / g) * ((size + 1 - k) as u128 / (k as u128 / g));
+        f[k - 1] += c - old.get(k - 1).copied().unwrap_or(0);
+    }
+}
+
+// The maximal non-empty simplices among the given ones, sorted by decreasing size
+fn maximal(mut masks: Vec<u128>) -> Vec<u128> {
+    masks.retain(|m| *m != 0);
+    masks.sort_unstable_by_key(|m| (Reverse(m.count_ones()), *m));
+    masks.dedup();
+
+    let mut out = Vec::<u128>::with_capacity(masks.len());
+    for m in masks {
+        if out.iter().all(|n| m & !n != 0) {
+            out.push(m);
+        }
+    }
+
+    out
+}
@@ -0,0 +1,58 @@
+use rayon::prelude::*;
+
+use crate::{Face, SimplicialComplex, Vertex};
+
+// Complexes of finite metric spaces, such as point clouds, on the points 0..n with the given
+// distances. The distances are computed as they are needed, so that a point cloud need not be
+// turned into a distance matrix first.
+impl<Point: Vertex> SimplicialComplex<Point> {
+    // The Vietoris–Rips complex, whose faces are the sets of points at distance at most `scale`
+    // from each other. It is the clique complex of the graph of these pairs of points.
+    pub fn vietoris_rips<F>(n: usize, distance: F, scale: f64) -> Self
+    where
+        F: Fn(usize, usize) -> f64 + Sync,
+    {
+        let edges: Vec<(usize, usize)> = (0..n)
+            .into_par_iter()
+            .flat_map_iter(|i| {
+                // Every point is a vertex, even if it is isolated.
+                let distance = &distance;
+                std::iter::once((i, i)).chain(
+                    (i + 1..n)
+                        .filter(move |j| distance(i, *j) <= scale)
+                        .map(move |j| (i, j)),
+                )
+            })
+            .collect();
+
+        Self::clique_complex(edges.into_iter().map(|(i, j)| (label(i), label(j))))
+    }
+
+    // The Čech complex of the metric space, whose faces are the sets of points within `scale` of a
+    // common point of the space. This is the nerve of the cover of the points by the balls of
+    // radius `scale` around them, and its facets are among these balls, so it is found without
+    // computing any intersections. For a point cloud, the centers of the balls are thus
+    // restricted to the points rather than taken anywhere in space.
+    pub fn cech<F>(n: usize, distance: F, scale: f64) -> Self
+    where
+        F: Fn(usize, usize) -> f64 + Sync,
+    {
+        let balls: Vec<Face<Point>> = (0..n)
+            .into_par_iter()
+            .map(|i| {
+                (0..n)
+                    .filter(|j| *j == i || distance(i, *j) <= scale)
+                    .map(label)
+                    .collect()
+            })
+            .collect();
+
+        Self::from_check_unique(balls)
+    }
+}
+
+fn label<Point: Vertex>(i: usize) -> Point {
+    i.try_into()
+        .ok()
+        .expect("The vertex type is too small for the number of points.")
+}
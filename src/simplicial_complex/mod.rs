@@ -1,19 +1,29 @@
 use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign};
 use std::slice::{Iter, IterMut};
-use std::sync::{Arc, atomic};
+use std::sync::atomic;
 
 use rayon::prelude::*;
 
-use crate::ProgressBar;
 use crate::Vertex;
 use crate::helpers::{SCHashMap, SCHashSet, new_hm, new_hs, new_vd, new_vec, to_sorted_vec};
-use crate::io::{new_pb, new_spnr};
-use crate::style::upd_sty;
+use crate::progress_indicator::{ProgressObserver, Silent};
 use crate::{Debug, Default, Reverse, fmt, max};
 
+mod cliques;
+mod fundamental_group;
+mod history;
+mod invariants;
+mod metric;
 mod simplex;
+mod strategy;
 
+pub(crate) use cliques::maximal_cliques;
+pub use fundamental_group::Presentation;
+pub use history::History;
+pub(crate) use history::Step;
 pub use simplex::Face;
+pub use strategy::SubcomplexStrategy;
+use strategy::Accretion;
 
 #[derive(Clone, PartialEq, Eq)]
 pub struct SimplicialComplex<Point: Vertex> {
@@ -90,7 +100,7 @@ impl<Point: Vertex> BitAnd for &SimplicialComplex<Point> {
 	type Output = SimplicialComplex<Point>;
 
 	fn bitand(self, rhs: Self) -> SimplicialComplex<Point> {
-		self.intersection_with_complex(rhs, true)
+		self.intersection_with_complex(rhs, None, &Silent).unwrap()
 	}
 }
 
@@ -220,82 +230,135 @@ impl<Point: Vertex> SimplicialComplex<Point> {
 		)
 	}
 
-	// Refactor the pb to be an optional parameter
-	fn intersection_with_complex(&self, other: &Self, quiet: bool) -> Self {
-		let pb: ProgressBar;
-		if quiet {
-			pb = ProgressBar::hidden();
-		} else {
-			pb = new_pb(self.len());
-			pb.set_message(upd_sty("Intersecting facets".to_string()));
-		}
+	// Return `None` if interrupted.
+	fn intersection_with_complex(
+		&self,
+		other: &Self,
+		intrpt: Option<&atomic::AtomicBool>,
+		progress: &dyn ProgressObserver,
+	) -> Option<Self> {
+		progress.stage_started("Intersecting facets", Some(self.len()));
 
 		let mut int_faces = new_hs::<Face<Point>>(max(self.len(), other.len()));
 
 		for facet in &self.facets {
+			if intrpt.is_some_and(|s| s.load(atomic::Ordering::Relaxed)) {
+				progress.stage_finished();
+				return None;
+			}
 			int_faces.extend(
 				other
 				.facets
 				.iter()
 				.filter_map(|g| g.maybe_intersection(facet)),
 			);
-			pb.inc(1);
+			progress.items_processed(1);
 		}
-		pb.finish();
+		progress.stage_finished();
 
-		Self::from_check_maximal(int_faces)
+		Some(Self::from_check_maximal(int_faces))
 	}
 
-	fn nerve(&self) -> Self {
-		Self::from_check_maximal(self.vertex_set().into_iter().map(|v| {
-			(0..self.len())
-			.filter(|&i| self.facets[i].contains(&v))
-			.map(|i| {
-				i.try_into()
-				.ok()
-				.expect("The number of facets has overflowed.")
-			})
-			.collect::<Face<Point>>()
-		}))
+	pub fn nerve(&self) -> Self {
+		self.interruptible_nerve(None).unwrap()
+	}
+
+	// Return `None` if interrupted.
+	fn interruptible_nerve(&self, intrpt: Option<&atomic::AtomicBool>) -> Option<Self> {
+		let vertex_set = self.vertex_set();
+		let mut faces = new_vec::<Face<Point>>(vertex_set.len());
+		for v in vertex_set {
+			if intrpt.is_some_and(|s| s.load(atomic::Ordering::Relaxed)) {
+				return None;
+			}
+			faces.push(
+				(0..self.len())
+				.filter(|&i| self.facets[i].contains(&v))
+				.map(|i| {
+					i.try_into()
+					.ok()
+					.expect("The number of facets has overflowed.")
+				})
+				.collect::<Face<Point>>(),
+			);
+		}
+
+		// Vertices which lie in the same facets give the same face.
+		Some(Self::from_check_unique(faces))
 	}
 
 	// Take Čech nerves until both the dimension and the number of vertices are minimized.
 	// Return the number of times the nerve was taken. It's not important, but it's nice for the
 	// user's edification.
-	pub fn nerve_reduce(&mut self, quiet: bool) -> usize {
-		let mut n = 0;
-		let spnr: ProgressBar;
-		if quiet {
-			spnr = ProgressBar::hidden();
-		} else {
-			spnr = new_spnr();
-			spnr.set_message(upd_sty(format!["Reduced with Čech nerves {n} times"]));
-		}
+	pub fn nerve_reduce(
+		&mut self,
+		intrpt: Option<&atomic::AtomicBool>,
+		progress: &dyn ProgressObserver,
+	) -> usize {
+		self.nerve_reduce_recording(intrpt, None, progress)
+	}
 
+	// Reduce with nerves as `nerve_reduce` does. The facets of each complex replaced with its
+	// nerve are recorded in `history` if given.
+	fn nerve_reduce_recording(
+		&mut self,
+		intrpt: Option<&atomic::AtomicBool>,
+		mut history: Option<&mut History<Point>>,
+		progress: &dyn ProgressObserver,
+	) -> usize {
+		let mut n = 0;
 		let mut base_vertex_count = self.vertex_set().len();
 		if base_vertex_count == 0 {
 			return 0;
 		}
-		let mut nerve = self.nerve();
+
+		progress.stage_started("Reduced with Čech nerves 0 times", None);
+		let Some(mut nerve) = self.interruptible_nerve(intrpt) else {
+			progress.stage_finished();
+			return 0;
+		};
+		// If we are interrupted, we keep whichever of `self` and `nerve` the last comparison
+		// favored. Both have the homotopy type of the input.
+		let mut interrupted = false;
 		while (n % 2 == 0 && (nerve.height() < self.height() || nerve.len() < base_vertex_count))
 			|| (n % 2 != 0 && (nerve.height() > self.height() || nerve.len() > base_vertex_count))
 			{
 				if n % 2 == 0 {
-					*self = nerve.nerve();
+					let Some(nerve_nerve) = nerve.interruptible_nerve(intrpt) else {
+						interrupted = true;
+						break;
+					};
+					let old = std::mem::replace(self, nerve_nerve);
+					if let Some(history) = history.as_deref_mut() {
+						history.steps.push(Step::Nerve(old.facets));
+						history.steps.push(Step::Nerve(nerve.facets.clone()));
+					}
 					base_vertex_count = self.vertex_set().len();
 				} else {
-					nerve = self.nerve();
+					let Some(new_nerve) = self.interruptible_nerve(intrpt) else {
+						interrupted = true;
+						break;
+					};
+					nerve = new_nerve;
 				}
 				n += 1;
-
-				if !quiet {
-					spnr.set_message(upd_sty(format!["Simplified with Čech nerves {n} times"]));
-				}
+				progress.items_processed(1);
+				progress.status(&format!["Simplified with Čech nerves {n} times"]);
 			}
-			if n % 2 != 0 {
-				*self = nerve;
+			// When the loop ends with `n` odd, the last nerve of `self` was no improvement. When
+			// we are interrupted with `n` even, `nerve` is the better complex.
+			if (n % 2 != 0) != interrupted {
+				let old = std::mem::replace(self, nerve);
+				if let Some(history) = history {
+					// With `n` odd, the last step recorded the nerve of `nerve`, which is dropped.
+					if interrupted {
+						history.steps.push(Step::Nerve(old.facets));
+					} else {
+						history.steps.pop();
+					}
+				}
 			}
-			spnr.finish();
+			progress.stage_finished();
 
 			n
 	}
@@ -361,56 +424,77 @@ impl<Point: Vertex> SimplicialComplex<Point> {
 		}
 	}
 
-	// Make the pb an optional parameter
-	fn enlarge_from_complex(&mut self, other: &mut Self, care: bool, quiet: bool) -> bool {
+	fn enlarge_from_complex(
+		&mut self,
+		other: &mut Self,
+		care: bool,
+		strategy: SubcomplexStrategy,
+		accretion: Accretion,
+		intrpt: Option<&atomic::AtomicBool>,
+		progress: &dyn ProgressObserver,
+	) -> bool {
 		let facet_count = other.len();
 		if self.height() == 0 {
 			return other.height() == 0;
 		}
 
 		let mut n = 0;
-		let spnr: ProgressBar;
-		if quiet {
-			spnr = ProgressBar::hidden();
-		} else {
-			spnr = new_spnr();
-			spnr.set_message(upd_sty(format![
-				"Added {n} of {facet_count} facets to the subcomplex"
-			]));
-		}
+		progress.stage_started(
+			&format!["Added {n} of {facet_count} facets to the subcomplex"],
+			None,
+		);
 
 		let check: bool = care && self.facets.par_iter().any(|f| other.has_face(f));
 		let other_facets = &mut other.facets;
 
-		let mut queue = new_vd::<Face<Point>>(facet_count);
-		queue.extend(
-			other_facets.extract_if(.., |of| !self.facets.iter().all(|sf| of.is_disjoint(sf))),
-		);
+		let mut queue = strategy.queue::<Point>(facet_count);
+		for of in
+			other_facets.extract_if(.., |of| !self.facets.iter().all(|sf| of.is_disjoint(sf)))
+		{
+			queue.push(of, |of| {
+				self.facets
+					.iter()
+					.map(|sf| sf.intersection(of).len())
+					.max()
+					.unwrap_or(0)
+			});
+		}
+
+		while let Some(facet) = queue.pop() {
+			// If we are interrupted, the facets accreted so far still form a valid subcomplex, so
+			// we return the rest to `other`.
+			if intrpt
+				.is_some_and(|s| s.load(atomic::Ordering::Relaxed))
+			{
+				other_facets.push(facet);
+				while let Some(facet) = queue.pop() {
+					other_facets.push(facet);
+				}
+				break;
+			}
 
-		while let Some(facet) = queue.pop_front() {
 			let intrsct = self.intersection_with_simplex(&facet);
-			if intrsct.is_contractible() {
+			if accretion.accepts(&intrsct) {
 				self.facets.push(facet);
 				let facet = self.facets.last().unwrap();
 
 				// Parallelize
-				queue.extend(other_facets.extract_if(.., |of| {
+				for of in other_facets.extract_if(.., |of| {
 					facet
 					.maybe_intersection(of)
 					.is_some_and(|f| !intrsct.has_face(&f))
-				}));
-
-				if !quiet {
-					n += 1;
-					spnr.set_message(upd_sty(format![
-						"Added {n} of {facet_count} facets to the subcomplex"
-					]));
+				}) {
+					queue.push(of, |of| facet.intersection(of).len());
 				}
+
+				n += 1;
+				progress.items_processed(1);
+				progress.status(&format!["Added {n} of {facet_count} facets to the subcomplex"]);
 			} else {
 				other_facets.push(facet);
 			}
 		}
-		spnr.finish();
+		progress.stage_finished();
 
 		if check {
 			self.uniqueify();
@@ -421,7 +505,14 @@ impl<Point: Vertex> SimplicialComplex<Point> {
 	}
 
 	fn is_deformation_retract(&mut self, other: &mut Self) -> bool {
-		self.enlarge_from_complex(other, false, true)
+		self.enlarge_from_complex(
+			other,
+			false,
+			SubcomplexStrategy::Fifo,
+			Accretion::Contractible,
+			None,
+			&Silent,
+		)
 		// retract_test(self.iter().collect(), other.iter().collect())
 	}
 
@@ -463,14 +554,90 @@ impl<Point: Vertex> SimplicialComplex<Point> {
 		Self::from_iter([self.facets.remove(0)])
 	}
 
-	pub fn contractible_subcomplex(&mut self, quiet: bool) -> Self {
-		let mut contractible = Self::from_iter([self.facets.remove(0)]);
-		contractible.enlarge_from_complex(self, false, quiet);
+	pub fn contractible_subcomplex(
+		&mut self,
+		strategy: SubcomplexStrategy,
+		intrpt: Option<&atomic::AtomicBool>,
+		progress: &dyn ProgressObserver,
+	) -> Self {
+		self.accrete_subcomplex(strategy, Accretion::Contractible, intrpt, progress)
+	}
+
+	// Accrete a subcomplex that is acyclic over F_p but not necessarily contractible. Many more
+	// facets can be accreted this way, but the resulting pair only has the homology of `self` over
+	// F_p rather than its homotopy type.
+	pub fn acyclic_subcomplex(
+		&mut self,
+		strategy: SubcomplexStrategy,
+		modulus: u32,
+		intrpt: Option<&atomic::AtomicBool>,
+		progress: &dyn ProgressObserver,
+	) -> Self {
+		self.accrete_subcomplex(strategy, Accretion::Acyclic(modulus), intrpt, progress)
+	}
+
+	// Keep accreting facets of `self` onto `sub`, e.g. to resume an interrupted accretion. The
+	// subcomplex is only acyclic over F_p if `acyclic` is `Some(p)`.
+	pub fn enlarge_subcomplex(
+		&mut self,
+		sub: &mut Self,
+		strategy: SubcomplexStrategy,
+		acyclic: Option<u32>,
+		intrpt: Option<&atomic::AtomicBool>,
+		progress: &dyn ProgressObserver,
+	) {
+		let accretion = acyclic.map_or(Accretion::Contractible, Accretion::Acyclic);
+		sub.enlarge_from_complex(self, false, strategy, accretion, intrpt, progress);
+	}
+
+	fn accrete_subcomplex(
+		&mut self,
+		strategy: SubcomplexStrategy,
+		accretion: Accretion,
+		intrpt: Option<&atomic::AtomicBool>,
+		progress: &dyn ProgressObserver,
+	) -> Self {
+		// The seeds are removed from last to first so that the remaining indices stay valid.
+		let mut seeds = strategy.seeds(&self.facets);
+		seeds.sort_unstable_by_key(|i| Reverse(*i));
+		let mut subcomplexes: Vec<Self> = seeds
+			.into_iter()
+			.map(|i| Self::from_iter([self.facets.remove(i)]))
+			.collect();
+		subcomplexes.reverse();
+		for sub in &mut subcomplexes {
+			sub.enlarge_from_complex(self, false, strategy, accretion, intrpt, progress);
+		}
+
+		// Each subcomplex is contractible (or acyclic), so accreting the others' facets onto the
+		// first yields a contractible (or acyclic) complex. The facets that cannot be accreted are
+		// returned to `self`, where they may still be picked up by a final accretion.
+		let mut accreted = subcomplexes.remove(0);
+		if !subcomplexes.is_empty() {
+			for mut sub in subcomplexes {
+				accreted.enlarge_from_complex(
+					&mut sub,
+					false,
+					strategy,
+					accretion,
+					intrpt,
+					&Silent,
+				);
+				self.facets.append(&mut sub.facets);
+			}
+			self.sortify();
+			accreted.enlarge_from_complex(self, false, strategy, accretion, intrpt, progress);
+		}
 
-		contractible
+		accreted
 	}
 
 	pub fn relabel_vertices(&mut self) {
+		let vert_dict = self.relabeling();
+		self.relabel_from_map(&vert_dict);
+	}
+
+	fn relabeling(&self) -> SCHashMap<Point, Point> {
 		let vertex_set = self.vertex_set();
 		let mut vert_dict = new_hm::<Point, Point>(vertex_set.len());
 		let mut n = Point::zero();
@@ -478,8 +645,13 @@ impl<Point: Vertex> SimplicialComplex<Point> {
 			vert_dict.insert(v, n);
 			n += Point::one();
 		}
+
+		vert_dict
+	}
+
+	fn relabel_from_map(&mut self, vert_dict: &SCHashMap<Point, Point>) {
 		for facet in &mut self.facets {
-			facet.replace_verts_from_map(&vert_dict);
+			facet.replace_verts_from_map(vert_dict);
 		}
 	}
 
@@ -497,32 +669,247 @@ impl<Point: Vertex> SimplicialComplex<Point> {
 		}
 	}
 
-	pub fn minimize_pair(&self, sub: Self) -> Self {
-		self.intersection_with_complex(&sub, false)
+	// Return `None` if interrupted.
+	pub fn minimize_pair(
+		&self,
+		sub: &Self,
+		intrpt: Option<&atomic::AtomicBool>,
+		progress: &dyn ProgressObserver,
+	) -> Option<Self> {
+		self.intersection_with_complex(sub, intrpt, progress)
 	}
 
-	pub fn pinch(&mut self, intrpt: Option<Arc<atomic::AtomicBool>>, quiet: bool) -> bool {
+	// Whether the edge `{a, b}` exists and satisfies the link condition, i.e. every face of the
+	// links of both `a` and `b` lies in the link of the edge. If it does, contracting the edge
+	// preserves the homotopy type.
+	fn satisfies_link_condition(&self, a: &Point, b: &Point) -> bool {
+		let mut a_star = new_vec::<&Face<Point>>(0);
+		let mut b_star = new_vec::<&Face<Point>>(0);
+		let mut edge_star = new_vec::<&Face<Point>>(0);
+		for facet in &self.facets {
+			match (facet.contains(a), facet.contains(b)) {
+				(true, true) => edge_star.push(facet),
+				(true, false) => a_star.push(facet),
+				(false, true) => b_star.push(facet),
+				_ => (),
+			}
+		}
+
+		// Faces of the links which come from facets containing the edge are trivially in the link
+		// of the edge, so only the facets containing exactly one of `a` and `b` are checked.
+		!edge_star.is_empty()
+			&& a_star.par_iter().all(|f| {
+				b_star.iter().all(|g| {
+					let face = f.intersection(g);
+					face.is_empty() || edge_star.iter().any(|h| face.leq(h))
+				})
+			})
+	}
+
+	// Identify `old` with `new`.
+	fn contract_edge(&mut self, old: &Point, new: Point) {
+		// Only facets containing `new` afterwards can become redundant.
+		let mut star: Vec<Face<Point>> = self
+			.facets
+			.extract_if(.., |f| f.contains(old) || f.contains(&new))
+			.collect();
+		for facet in &mut star {
+			if facet.remove(old) {
+				facet.insert(new);
+			}
+		}
+		self.facets.extend(Self::from_check_unique(star));
+		self.sortify();
+	}
+
+	// Contract the edges of `self` meeting `sub` which satisfy the link condition, without
+	// changing the homotopy type of the pair. An edge with exactly one vertex in `sub` is
+	// contracted onto that vertex, leaving `sub` unchanged. An edge with both vertices in `sub`
+	// must be an edge of `sub` satisfying the link condition there too, and is contracted in both
+	// complexes. Return the number of contracted edges.
+	fn contract_edges_near(&mut self, sub: &mut Self, intrpt: Option<&atomic::AtomicBool>) -> usize {
+		let frozen = sub.vertex_set();
+		let mut removed = new_hs::<Point>(0);
+		let mut n = 0;
+		for (u, adj) in self.edge_table() {
+			if intrpt.is_some_and(|s| s.load(atomic::Ordering::Relaxed)) {
+				break;
+			}
+			for v in adj {
+				if removed.contains(&u) {
+					break;
+				}
+				if removed.contains(&v) {
+					continue;
+				}
+
+				let (old, new) = match (frozen.contains(&u), frozen.contains(&v)) {
+					(false, false) => continue,
+					(true, false) => (v, u),
+					(_, true) => (u, v),
+				};
+				if frozen.contains(&old) {
+					if !(sub.satisfies_link_condition(&old, &new)
+						&& self.satisfies_link_condition(&old, &new))
+					{
+						continue;
+					}
+					sub.contract_edge(&old, new);
+				} else if !self.satisfies_link_condition(&old, &new) {
+					continue;
+				}
+				self.contract_edge(&old, new);
+				removed.insert(old);
+				n += 1;
+			}
+		}
+
+		n
+	}
+
+	// Shrink the pair `(self, sub)` without changing its homotopy type (or, if `acyclic` is given,
+	// its homology over that field) by repeating the following steps until the pair stops losing
+	// facets:
+	// 1. pinch the edges of `self` whose removed vertex does not lie in `sub`, and contract the
+	//    edges meeting `sub` which satisfy the link condition;
+	// 2. accrete facets of `self` onto `sub`, obtaining a larger subcomplex `D` for which the pair
+	//    `(D, sub)` is contractible (or acyclic);
+	// 3. excise, replacing the pair with the facets of `self` not in `D` and their intersection
+	//    with `D`.
+	// A round which does not shrink the pair is discarded, so the pair never grows. Return the
+	// number of rounds which reduced the pair.
+	pub fn reduce_pair(
+		&mut self,
+		sub: &mut Self,
+		acyclic: Option<u32>,
+		intrpt: Option<&atomic::AtomicBool>,
+		progress: &dyn ProgressObserver,
+	) -> usize {
+		let accretion = acyclic.map_or(Accretion::Contractible, Accretion::Acyclic);
+		let is_interrupted = || {
+			intrpt
+				.is_some_and(|s| s.load(atomic::Ordering::Relaxed))
+		};
+
+		let mut n = 0;
+		progress.stage_started("Reduced the pair 0 times", None);
+
+		// Accretion leaves no facets at all in `self` once it takes the whole complex, in which
+		// case the pair is trivial.
+		if self.facets.is_empty() {
+			*self = Self::default();
+			*sub = Self::default();
+		}
+
+		while !self.is_empty() && !sub.is_empty() && !is_interrupted() {
+			let facet_count = self.len() + sub.len();
+
+			// The round runs on copies of the pair, which only replace it if they are smaller.
+			let mut reduced = self.clone();
+			let mut reduced_sub = sub.clone();
+			let finished = reduced.reduce_pair_once(&mut reduced_sub, accretion, intrpt);
+			if !reduced.is_empty() && reduced.len() + reduced_sub.len() >= facet_count {
+				break;
+			}
+			*self = reduced;
+			*sub = reduced_sub;
+
+			n += 1;
+			progress.items_processed(1);
+			progress.status(&format!["Reduced the pair {n} times"]);
+			if !finished {
+				break;
+			}
+		}
+		progress.stage_finished();
+
+		n
+	}
+
+	// A round of `reduce_pair`. Return whether it ran to the end, rather than stopping early
+	// because it was interrupted or the intersection could not be taken.
+	fn reduce_pair_once(
+		&mut self,
+		sub: &mut Self,
+		accretion: Accretion,
+		intrpt: Option<&atomic::AtomicBool>,
+	) -> bool {
+		self.pinch_relative(Some(sub), intrpt, None, &Silent);
+		if intrpt
+			.is_some_and(|s| s.load(atomic::Ordering::Relaxed))
+		{
+			return false;
+		}
+		if self.contract_edges_near(sub, intrpt) > 0 {
+			let vert_dict = self.relabeling();
+			self.relabel_from_map(&vert_dict);
+			sub.relabel_from_map(&vert_dict);
+		}
+
+		let mut accreted = sub.clone();
+		accreted.enlarge_from_complex(
+			self,
+			true,
+			SubcomplexStrategy::Fifo,
+			accretion,
+			intrpt,
+			&Silent,
+		);
+		if self.facets.is_empty() {
+			// The whole complex was accreted, so the pair is trivial.
+			*self = Self::default();
+			*sub = Self::default();
+		} else if let Some(intrsct) =
+			self.intersection_with_complex(&accreted, intrpt, &Silent)
+		{
+			self.sortify();
+			*sub = intrsct;
+		} else {
+			// The pair `(self ∪ accreted, accreted)` is equivalent to the original pair, so we
+			// can stop there without excising.
+			self.facets.extend(accreted.facets.iter().cloned());
+			self.uniqueify();
+			self.maximalify();
+			*sub = accreted;
+			return false;
+		}
+
+		true
+	}
+
+	// Return the number of pinched edges.
+	pub fn pinch(
+		&mut self,
+		intrpt: Option<&atomic::AtomicBool>,
+		progress: &dyn ProgressObserver,
+	) -> usize {
+		self.pinch_relative(None, intrpt, None, progress)
+	}
+
+	// Pinch edges without removing any vertex of `sub`, so that `sub` remains a subcomplex and
+	// the homotopy type of the pair is preserved. The vertices of `sub` are relabeled alongside
+	// those of `self`. The contractions and the relabeling are recorded in `history` if given.
+	fn pinch_relative(
+		&mut self,
+		mut sub: Option<&mut Self>,
+		intrpt: Option<&atomic::AtomicBool>,
+		mut history: Option<&mut History<Point>>,
+		progress: &dyn ProgressObserver,
+	) -> usize {
 		if self.is_empty()
 			|| intrpt
-			.as_ref()
 			.is_some_and(|s| s.load(atomic::Ordering::Relaxed))
 			{
-				return false;
+				return 0;
 			}
 			let mut interrupted = false;
-		let mut pinched = false;
 
+		let frozen = sub.as_ref().map_or_else(|| new_hs(0), |sub| sub.vertex_set());
 		let edges = self.edge_table();
 		let vertex_count = edges.len();
 		let mut n: usize = 0;
 
-		let pb: ProgressBar;
-		if quiet {
-			pb = ProgressBar::hidden();
-		} else {
-			pb = new_pb(vertex_count);
-			pb.set_message(upd_sty(format!["Pinched {n} edges"]));
-		}
+		progress.stage_started("Pinched 0 edges", Some(vertex_count));
 
 		rayon::ThreadPoolBuilder::new()
 		.stack_size(1024 * 1024 * 2)
@@ -530,11 +917,13 @@ impl<Point: Vertex> SimplicialComplex<Point> {
 		.unwrap()
 		.install(|| {
 			'outer: for (old, adj) in edges {
-				pb.inc(1);
+				progress.items_processed(1);
+				if frozen.contains(&old) {
+					continue;
+				}
 
 				for new in adj {
 					if intrpt
-						.as_ref()
 						.is_some_and(|s| s.load(atomic::Ordering::Relaxed))
 						{
 							interrupted = true;
@@ -599,6 +988,21 @@ impl<Point: Vertex> SimplicialComplex<Point> {
 									 pre_int_facets,
 						)
 					} {
+						if let Some(history) = history.as_deref_mut() {
+							let star = edge_link
+							.iter()
+							.map(|i| (i, [old, new]))
+							.chain(old_link_ext.iter().map(|i| (i, [old, old])))
+							.chain(new_link_ext.iter().map(|i| (i, [new, new])))
+							.map(|(i, edge)| {
+								let mut facet = self.facets[*i].clone();
+								facet.extend(edge);
+								facet
+							})
+							.collect();
+							history.steps.push(Step::Contraction { old, new, star });
+						}
+
 						new_link_ext.sort_by_key(|i| Reverse(self.facets[*i].len()));
 						old_link_ext.extend(edge_link);
 						let mut rem_or_ins = new_vec::<(usize, bool)>(old_link_ext.len());
@@ -617,22 +1021,35 @@ impl<Point: Vertex> SimplicialComplex<Point> {
 
 						drop(pre_int_faces);
 
-						new_link_ext
-						.into_iter()
-						.chain(rem_or_ins.extract_if(.., |(_, b)| !*b).map(|p| p.0))
-						.for_each(|i| {
-							self.facets[i].insert(new);
+						let moved: Vec<usize> = rem_or_ins
+						.extract_if(.., |(_, b)| !*b)
+						.map(|p| p.0)
+						.collect();
+						new_link_ext.iter().chain(&moved).for_each(|i| {
+							self.facets[*i].insert(new);
 						});
 
-						rem_or_ins.sort_unstable_by_key(|p| Reverse(p.0));
-						rem_or_ins.into_iter().for_each(|(i, _)| {
+						// A facet which contained `old` can end up inside another facet containing
+						// `new`, or the other way round, so these are removed to keep the facets
+						// maximal.
+						let mut removed: Vec<usize> = rem_or_ins.into_iter().map(|p| p.0).collect();
+						removed.extend(new_link_ext.iter().copied().filter(|&j| {
+							moved.iter().any(|&i| self.facets[j].leq(&self.facets[i]))
+						}));
+						removed.extend(moved.iter().copied().filter(|&i| {
+							new_link_ext.iter().chain(&moved).any(|&j| {
+								self.facets[i].len() < self.facets[j].len()
+								&& self.facets[i].leq(&self.facets[j])
+							})
+						}));
+						removed.sort_unstable_by_key(|i| Reverse(*i));
+						removed.into_iter().for_each(|i| {
 							self.facets.swap_remove(i);
 						});
 
 						n += 1;
-						pb.set_message(upd_sty(format!["Pinched {n} edges"]));
+						progress.status(&format!["Pinched {n} edges"]);
 
-						pinched = true;
 						break;
 					} else {
 						drop(pre_int_faces);
@@ -656,16 +1073,23 @@ impl<Point: Vertex> SimplicialComplex<Point> {
 		});
 
 		self.sortify();
+		progress.stage_finished();
 
 		if !interrupted {
-			pb.finish();
 
 			// The pinch algorithm is sensitive to the ordering of the vertices. Relabeling the
 			// vertices shakes things up to facilitate further pinches.
-			self.relabel_vertices();
+			let vert_dict = self.relabeling();
+			self.relabel_from_map(&vert_dict);
+			if let Some(sub) = sub.as_mut() {
+				sub.relabel_from_map(&vert_dict);
+			}
+			if let Some(history) = history {
+				history.steps.push(Step::Relabeling(vert_dict));
+			}
 		}
 
-		pinched
+		n
 	}
 }
 
@@ -0,0 +1,178 @@
+use std::collections::BinaryHeap;
+
+use crate::Vertex;
+use crate::helpers::{SCHashMap, VecDeque, new_hm, new_vd, new_vec};
+use crate::progress_indicator::Silent;
+use crate::{Face, Ordering, Reverse, SimplicialComplex};
+
+// Heuristics for choosing where a contractible subcomplex starts growing and in which order it
+// absorbs the remaining facets. The subcomplex is contractible whichever strategy is used; only
+// its size, and therefore the size of the resulting pair, differs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SubcomplexStrategy {
+    // Seed from the largest facet and accrete facets in the order in which they are discovered.
+    #[default]
+    Fifo,
+    // Seed from the facet whose vertices lie in the most facets.
+    Degree,
+    // Seed from the facet which meets the most other facets.
+    Neighbors,
+    // Seed from the largest facet and accrete the facets with the largest overlaps first.
+    Overlap,
+    // Grow up to this many subcomplexes from pairwise disjoint facets and merge them afterwards.
+    Seeds(usize),
+}
+
+impl SubcomplexStrategy {
+    // The indices of the facets from which the subcomplexes are grown. The facets are assumed to
+    // be sorted by decreasing size.
+    pub(super) fn seeds<Point: Vertex>(&self, facets: &[Face<Point>]) -> Vec<usize> {
+        match self {
+            Self::Fifo | Self::Overlap => vec![0],
+            Self::Degree => {
+                let mut degrees: SCHashMap<Point, usize> = new_hm(facets.len());
+                for v in facets.iter().flatten() {
+                    *degrees.entry(*v).or_default() += 1;
+                }
+
+                vec![argmax(
+                    facets.iter().map(|f| f.iter().map(|v| degrees[v]).sum()),
+                )]
+            }
+            Self::Neighbors => {
+                let mut incidences: SCHashMap<Point, Vec<usize>> = new_hm(facets.len());
+                for (i, f) in facets.iter().enumerate() {
+                    for v in f {
+                        incidences.entry(*v).or_default().push(i);
+                    }
+                }
+
+                // `seen[j] == i + 1` records that facet `j` has already been counted as a
+                // neighbor of facet `i`.
+                let mut seen = vec![0; facets.len()];
+                vec![argmax(facets.iter().enumerate().map(|(i, f)| {
+                    seen[i] = i + 1;
+                    let mut count = 0;
+                    for j in f.iter().flat_map(|v| &incidences[v]) {
+                        if seen[*j] != i + 1 {
+                            seen[*j] = i + 1;
+                            count += 1;
+                        }
+                    }
+
+                    count
+                }))]
+            }
+            Self::Seeds(n) => {
+                let mut seeds = new_vec::<usize>(*n);
+                for (i, f) in facets.iter().enumerate() {
+                    if seeds.len() == (*n).max(1) {
+                        break;
+                    }
+                    if seeds.iter().all(|j| f.is_disjoint(&facets[*j])) {
+                        seeds.push(i);
+                    }
+                }
+
+                seeds
+            }
+        }
+    }
+
+    pub(super) fn queue<Point: Vertex>(&self, len: usize) -> AccretionQueue<Point> {
+        match self {
+            Self::Overlap => AccretionQueue::Weighted(BinaryHeap::with_capacity(len), 0),
+            _ => AccretionQueue::Plain(new_vd(len)),
+        }
+    }
+}
+
+// The condition under which a facet is accreted onto a subcomplex: its intersection with the
+// subcomplex must be contractible or, if we only care about homology, acyclic over F_p.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Accretion {
+    Contractible,
+    Acyclic(u32),
+}
+
+impl Accretion {
+    pub(super) fn accepts<Point: Vertex>(&self, intrsct: &SimplicialComplex<Point>) -> bool {
+        match self {
+            Self::Contractible => intrsct.is_contractible(),
+            // Taking nerves preserves homology and usually shrinks the intersection enough that
+            // enumerating its faces is cheap.
+            Self::Acyclic(p) => {
+                intrsct.is_contractible() || {
+                    let mut sc = intrsct.clone();
+                    sc.nerve_reduce(None, &Silent);
+                    sc.is_acyclic(*p)
+                }
+            }
+        }
+    }
+}
+
+fn argmax(scores: impl Iterator<Item = usize>) -> usize {
+    // `max_by_key` returns the last maximum, but we prefer the first (and largest) facet.
+    scores
+        .enumerate()
+        .max_by_key(|(i, s)| (*s, Reverse(*i)))
+        .map_or(0, |(i, _)| i)
+}
+
+// A facet waiting to be accreted, weighted by its overlap with the subcomplex. Ties are broken in
+// favor of the facet which was discovered first.
+pub(super) struct Weighted<Point: Vertex> {
+    overlap: usize,
+    order: Reverse<usize>,
+    face: Face<Point>,
+}
+
+impl<Point: Vertex> PartialEq for Weighted<Point> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<Point: Vertex> Eq for Weighted<Point> {}
+
+impl<Point: Vertex> PartialOrd for Weighted<Point> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Point: Vertex> Ord for Weighted<Point> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.overlap, self.order).cmp(&(other.overlap, other.order))
+    }
+}
+
+pub(super) enum AccretionQueue<Point: Vertex> {
+    Plain(VecDeque<Face<Point>>),
+    Weighted(BinaryHeap<Weighted<Point>>, usize),
+}
+
+impl<Point: Vertex> AccretionQueue<Point> {
+    // `overlap` is only evaluated if the queue is weighted.
+    pub(super) fn push(&mut self, face: Face<Point>, overlap: impl FnOnce(&Face<Point>) -> usize) {
+        match self {
+            Self::Plain(queue) => queue.push_back(face),
+            Self::Weighted(heap, count) => {
+                heap.push(Weighted {
+                    overlap: overlap(&face),
+                    order: Reverse(*count),
+                    face,
+                });
+                *count += 1;
+            }
+        }
+    }
+
+    pub(super) fn pop(&mut self) -> Option<Face<Point>> {
+        match self {
+            Self::Plain(queue) => queue.pop_front(),
+            Self::Weighted(heap, _) => heap.pop().map(|w| w.face),
+        }
+    }
+}
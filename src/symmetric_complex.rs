@@ -0,0 +1,379 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use itertools::Itertools;
+
+use crate::helpers::{SCHashMap, SCHashSet, new_hm, new_hs, new_vd};
+use crate::progress_indicator::ProgressObserver;
+use crate::{Face, SimplicialComplex};
+
+// A complex with a group action, given by generating permutations of the vertices and one
+// representative of each orbit of facets. The invariants below are computed one orbit at a time,
+// so only the faces of a single orbit are held in memory at once, rather than the whole complex.
+// The orbits are found by applying the generators until no new face appears, so this pays off for
+// complexes with many facets in few orbits, e.g. `partial_bijection_complex`.
+#[derive(Clone, Debug)]
+pub struct SymmetricComplex {
+    // Each generator maps a vertex `v` to `generator[v]`, and fixes the vertices past its end.
+    generators: Vec<Vec<u32>>,
+    // The least facet of each orbit as a sorted tuple, sorted by decreasing size. As for
+    // `SimplicialComplex`, the empty complex has a single, empty facet.
+    representatives: Vec<Vec<u32>>,
+}
+
+// The orbit of a face, oriented by the order of its vertices
+pub(crate) struct Orbit {
+    // The least face of the orbit, as a sorted tuple
+    pub(crate) representative: Vec<u32>,
+    pub(crate) size: usize,
+    // Whether the face is mapped to the representative with the opposite orientation
+    pub(crate) reversed: bool,
+    // Whether no permutation maps the representative to itself with the opposite orientation.
+    // Otherwise the orbit vanishes in the chain complex of coinvariants over fields of odd
+    // characteristic.
+    pub(crate) orientable: bool,
+}
+
+impl SymmetricComplex {
+    // The complex generated by the orbits of `facets`. Faces whose orbits lie in larger orbits
+    // are dropped, so the given facets need not be maximal.
+    pub fn new<T>(generators: Vec<Vec<u32>>, facets: T) -> Self
+    where
+        T: IntoIterator<Item = Face<u32>>,
+    {
+        for g in &generators {
+            assert![
+                g.iter().copied().sorted_unstable().eq(0..g.len() as u32),
+                "A generator should be a permutation of its vertices."
+            ];
+        }
+        let mut sc = Self {
+            generators,
+            representatives: Vec::new(),
+        };
+
+        let mut representatives = facets
+            .into_iter()
+            .map(|f| sc.orbit(&f.tuple()).representative)
+            .collect_vec();
+        representatives.sort_unstable_by(|f, g| g.len().cmp(&f.len()).then_with(|| f.cmp(g)));
+        representatives.dedup();
+        // An orbit lies in a larger one if one of its faces lies in the representative of the
+        // larger orbit.
+        for r in representatives {
+            let larger = sc
+                .representatives
+                .iter()
+                .take_while(|s| s.len() > r.len())
+                .collect_vec();
+            if larger.is_empty()
+                || !sc
+                    .orbit_faces(&r)
+                    .keys()
+                    .any(|f| larger.iter().any(|s| is_subtuple(f, s)))
+            {
+                sc.representatives.push(r);
+            }
+        }
+        if sc.representatives.is_empty() {
+            sc.representatives.push(Vec::new());
+        }
+
+        sc
+    }
+
+    pub fn generators(&self) -> &[Vec<u32>] {
+        &self.generators
+    }
+
+    pub fn representatives(&self) -> &[Vec<u32>] {
+        &self.representatives
+    }
+
+    // The number of vertices of the largest facet
+    pub fn height(&self) -> usize {
+        self.representatives[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.height() == 0
+    }
+
+    // The dimension of the complex, which is -1 for the empty complex
+    pub fn dimension(&self) -> isize {
+        self.height() as isize - 1
+    }
+
+    pub fn is_pure(&self) -> bool {
+        self.representatives.iter().map(|f| f.len()).all_equal()
+    }
+
+    // The faces of the orbit of `face`, with whether each is the image of `face` with the
+    // opposite orientation, and whether the orbit is orientable
+    fn explore(&self, face: &[u32]) -> (SCHashMap<Vec<u32>, bool>, bool) {
+        let mut parity = new_hm::<Vec<u32>, bool>(1);
+        let mut queue = new_vd::<Vec<u32>>(1);
+        let mut orientable = true;
+        parity.insert(face.to_vec(), false);
+        queue.push_back(face.to_vec());
+        while let Some(f) = queue.pop_front() {
+            let odd = parity[&f];
+            for g in &self.generators {
+                let (image, flip) = apply(g, &f);
+                match parity.get(&image) {
+                    Some(other) => orientable &= *other == (odd ^ flip),
+                    None => {
+                        parity.insert(image.clone(), odd ^ flip);
+                        queue.push_back(image);
+                    }
+                }
+            }
+        }
+
+        (parity, orientable)
+    }
+
+    fn orbit_faces(&self, face: &[u32]) -> SCHashMap<Vec<u32>, bool> {
+        self.explore(face).0
+    }
+
+    // The orbit of a face given as a sorted tuple
+    pub(crate) fn orbit(&self, face: &[u32]) -> Orbit {
+        let (parity, orientable) = self.explore(face);
+        let (representative, reversed) = parity
+            .iter()
+            .min_by_key(|(f, _)| *f)
+            .map(|(f, odd)| (f.clone(), *odd))
+            .unwrap();
+
+        Orbit {
+            representative,
+            size: parity.len(),
+            reversed,
+            orientable,
+        }
+    }
+
+    // The orbits of the non-empty faces, indexed by the number of vertices of their faces
+    pub(crate) fn face_orbits(&self) -> Vec<Vec<Orbit>> {
+        let mut orbits: Vec<Vec<Orbit>> = (0..=self.height()).map(|_| Vec::new()).collect();
+        let mut seen: SCHashSet<Vec<u32>> = new_hs(self.representatives.len());
+        for facet in self.representatives.iter().filter(|f| !f.is_empty()) {
+            assert![
+                facet.len() < 64,
+                "The faces of facets with 64 vertices or more cannot be listed."
+            ];
+            for mask in 1..1u64 << facet.len() {
+                let face = (0..facet.len())
+                    .filter(|i| mask >> i & 1 == 1)
+                    .map(|i| facet[i])
+                    .collect_vec();
+                // A representative is the least face of its orbit, so it is found at once.
+                if seen.contains(&face) {
+                    continue;
+                }
+                let orbit = self.orbit(&face);
+                if seen.insert(orbit.representative.clone()) {
+                    orbits[face.len()].push(orbit);
+                }
+            }
+        }
+
+        orbits
+    }
+
+    // The numbers of faces of each dimension, starting from the vertices, as for
+    // `SimplicialComplex::f_vector`
+    pub fn f_vector(&self) -> Vec<u128> {
+        self.face_orbits()
+            .iter()
+            .skip(1)
+            .map(|o| o.iter().map(|o| o.size as u128).sum())
+            .collect()
+    }
+
+    pub fn euler_characteristic(&self) -> i128 {
+        self.f_vector()
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                if i % 2 == 0 {
+                    *f as i128
+                } else {
+                    -(*f as i128)
+                }
+            })
+            .sum()
+    }
+
+    // The representatives of the orbits of vertices
+    fn vertex_representatives(&self) -> Vec<u32> {
+        let mut seen: SCHashSet<u32> = new_hs(self.representatives.len());
+        let mut out = Vec::new();
+        for v in self.representatives.iter().flatten() {
+            if !seen.contains(v) {
+                let orbit = self.orbit_faces(&[*v]);
+                seen.extend(orbit.keys().map(|f| f[0]));
+                out.push(orbit.into_keys().min().unwrap()[0]);
+            }
+        }
+
+        out
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_representatives()
+            .iter()
+            .map(|v| self.orbit(&[*v]).size)
+            .sum()
+    }
+
+    // The number of facets, counting the empty facet of the empty complex
+    pub fn facet_count(&self) -> usize {
+        self.representatives
+            .iter()
+            .map(|f| self.orbit(f).size)
+            .sum()
+    }
+
+    // Every facet, one orbit at a time
+    pub fn facets(&self) -> impl Iterator<Item = Face<u32>> + '_ {
+        self.representatives
+            .iter()
+            .flat_map(|f| self.orbit_faces(f).into_keys())
+            .map(Face::from_iter)
+    }
+
+    pub fn to_complex(&self) -> SimplicialComplex<u32> {
+        SimplicialComplex::from_check_sorted(self.facets())
+    }
+
+    // The nerve of the cover by facets, on which the group acts by permuting the facets. The
+    // vertices of the nerve are labeled one orbit of facets at a time: the facets of each orbit
+    // get consecutive labels, and the generators are written on those labels while the orbit is
+    // held. Only a single orbit of facets is listed at once, and the generators of the nerve need
+    // one label per facet rather than the facet itself.
+    pub fn nerve(&self) -> Self {
+        self.interruptible_nerve(None).unwrap()
+    }
+
+    // Return `None` if interrupted.
+    fn interruptible_nerve(&self, intrpt: Option<&AtomicBool>) -> Option<Self> {
+        let vertices = self.vertex_representatives();
+        let mut generators: Vec<Vec<u32>> = vec![Vec::new(); self.generators.len()];
+        // The facets of the nerve which come from the representatives of the orbits of vertices
+        let mut faces: Vec<Vec<u32>> = vec![Vec::new(); vertices.len()];
+        let mut offset = 0u32;
+        for r in &self.representatives {
+            if intrpt.is_some_and(|s| s.load(Ordering::Relaxed)) {
+                return None;
+            }
+            let orbit = self
+                .orbit_faces(r)
+                .into_keys()
+                .sorted_unstable()
+                .collect_vec();
+            let index: SCHashMap<&[u32], u32> =
+                orbit.iter().map(|f| f.as_slice()).zip(offset..).collect();
+            for (g, labels) in self.generators.iter().zip(&mut generators) {
+                labels.extend(orbit.iter().map(|f| index[apply(g, f).0.as_slice()]));
+            }
+            for (v, face) in vertices.iter().zip(&mut faces) {
+                face.extend(
+                    (offset..)
+                        .zip(&orbit)
+                        .filter(|(_, f)| f.binary_search(v).is_ok())
+                        .map(|(i, _)| i),
+                );
+            }
+            offset = u32::try_from(orbit.len())
+                .ok()
+                .and_then(|n| offset.checked_add(n))
+                .expect("The number of facets has overflowed.");
+        }
+
+        Some(Self::new(
+            generators,
+            faces.into_iter().map(Face::from_iter),
+        ))
+    }
+
+    // Take nerves until both the dimension and the number of vertices are minimized, as in
+    // `SimplicialComplex::nerve_reduce`. Return the number of times the nerve was taken.
+    pub fn nerve_reduce(
+        &mut self,
+        intrpt: Option<&AtomicBool>,
+        progress: &dyn ProgressObserver,
+    ) -> usize {
+        let mut n = 0;
+        let mut base_vertex_count = self.vertex_count();
+        if base_vertex_count == 0 {
+            return 0;
+        }
+
+        progress.stage_started("Reduced with Čech nerves 0 times", None);
+        let Some(mut nerve) = self.interruptible_nerve(intrpt) else {
+            progress.stage_finished();
+            return 0;
+        };
+        let mut nerve_facet_count = nerve.facet_count();
+        // If we are interrupted, we keep whichever of `self` and `nerve` the last comparison
+        // favored, as in `SimplicialComplex::nerve_reduce`.
+        let mut interrupted = false;
+        while (n % 2 == 0
+            && (nerve.height() < self.height() || nerve_facet_count < base_vertex_count))
+            || (n % 2 != 0
+                && (nerve.height() > self.height() || nerve_facet_count > base_vertex_count))
+        {
+            if n % 2 == 0 {
+                let Some(nerve_nerve) = nerve.interruptible_nerve(intrpt) else {
+                    interrupted = true;
+                    break;
+                };
+                *self = nerve_nerve;
+                base_vertex_count = self.vertex_count();
+            } else {
+                let Some(new_nerve) = self.interruptible_nerve(intrpt) else {
+                    interrupted = true;
+                    break;
+                };
+                nerve = new_nerve;
+                nerve_facet_count = nerve.facet_count();
+            }
+            n += 1;
+            progress.items_processed(1);
+            progress.status(&format!["Simplified with Čech nerves {n} times"]);
+        }
+        // When the loop ends with `n` odd, the last nerve of `self` was no improvement. When we
+        // are interrupted with `n` even, `nerve` is the better complex.
+        if (n % 2 != 0) != interrupted {
+            *self = nerve;
+        }
+        progress.stage_finished();
+
+        n
+    }
+}
+
+// The image of a sorted tuple under a permutation, sorted, and whether sorting it took an odd
+// permutation
+fn apply(g: &[u32], face: &[u32]) -> (Vec<u32>, bool) {
+    let mut image = face
+        .iter()
+        .map(|v| g.get(*v as usize).copied().unwrap_or(*v))
+        .collect_vec();
+    let inversions = image
+        .iter()
+        .tuple_combinations()
+        .filter(|(a, b)| a > b)
+        .count();
+    image.sort_unstable();
+
+    (image, inversions % 2 == 1)
+}
+
+// Whether the sorted tuple `f` is contained in the sorted tuple `g`
+fn is_subtuple(f: &[u32], g: &[u32]) -> bool {
+    let mut rest = g.iter();
+
+    f.iter().all(|v| rest.any(|w| w == v))
+}
@@ -0,0 +1,259 @@
+// Runs the `sc-simplify` binary on small inputs, for behavior which lives outside the library.
+
+use sc_simplify::generators::{dunce_hat, klein_bottle, projective_plane, torus};
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_sc-simplify"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // The binary may exit without reading its input, e.g. on a usage error.
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn contractible_pairs() {
+    // Accretion takes the whole complex, which used to crash `--iterate-pair`, and the rayon
+    // workers of `--components`. The pair is then empty.
+    for input in ["50\n", "40 41\n", "0 1 2\n2 3\n"] {
+        for args in [
+            &["-q"][..],
+            &["-q", "--iterate-pair"],
+            &["-q", "--components", "--iterate-pair"],
+        ] {
+            let output = run(args, input);
+            assert![output.status.success(), "{args:?} on {input:?}"];
+            let stdout = String::from_utf8(output.stdout).unwrap();
+            assert![
+                stdout.trim().is_empty(),
+                "{args:?} on {input:?}: {stdout:?}"
+            ];
+        }
+    }
+}
+
+const PROJECTIVE_PLANE: &str =
+    "0 1 4\n0 1 5\n0 2 3\n0 2 5\n0 3 4\n1 2 3\n1 2 4\n1 3 5\n2 4 5\n3 4 5\n";
+
+fn assert_usage_error(args: &[&str], input: &str) {
+    let output = run(args, input);
+    assert_eq![output.status.code(), Some(2), "{args:?}"];
+    assert![output.stdout.is_empty(), "{args:?}"];
+    assert![
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .starts_with("error: ")
+    ];
+}
+
+#[test]
+fn incompatible_options() {
+    // These used to panic once the input had been read.
+    assert_usage_error(&["-q", "--verify", "2", "--acyclic", "3"], PROJECTIVE_PLANE);
+    assert_usage_error(
+        &["-q", "simplify", "--verify", "5", "--acyclic", "3"],
+        PROJECTIVE_PLANE,
+    );
+    assert_usage_error(&["-q", "--xml"], PROJECTIVE_PLANE);
+    assert![
+        run(&["-q", "--verify", "3", "--acyclic", "3"], PROJECTIVE_PLANE)
+            .status
+            .success()
+    ];
+    assert![
+        run(&["-q", "--xml", "--no-pair"], PROJECTIVE_PLANE)
+            .status
+            .success()
+    ];
+
+    // A run interrupted after accretion must print a pair, whose homology is only preserved over
+    // the field of its acyclic subcomplex.
+    let path = std::env::temp_dir().join(format!["sc-simplify-cli-{}", std::process::id()]);
+    std::fs::write(
+        &path,
+        "sc-simplify checkpoint\nstage minimize\npinch-loops 0\nacyclic 3\ncomplex\n0 1 2\n1 2 3\n\
+         subcomplex\n1 2\n",
+    )
+    .unwrap();
+    let resume = path.to_str().unwrap();
+    assert_usage_error(&["-q", "--resume", resume, "--verify", "2"], "");
+    assert_usage_error(&["-q", "--resume", resume, "--no-pair"], "");
+    assert![
+        run(&["-q", "--resume", resume, "--verify", "3"], "")
+            .status
+            .success()
+    ];
+    std::fs::remove_file(&path).unwrap();
+}
+
+fn stdout(output: Output) -> String {
+    assert![output.status.success()];
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn components_are_merged_into_an_equivalent_pair() {
+    // A torus, a Klein bottle, a projective plane, a dunce hat and a point, relabeled to be
+    // disjoint. Their reduced Betti numbers are 4 5 3 over F_2, and 4 3 1 over F_3.
+    let mut input = String::new();
+    for (i, sc) in [torus(), klein_bottle(), projective_plane(), dunce_hat()]
+        .iter()
+        .enumerate()
+    {
+        for f in sc {
+            let vertices: Vec<_> = f.iter().map(|v| (v + 10 * i as u32).to_string()).collect();
+            input += &(vertices.join(" ") + "\n");
+        }
+    }
+    input += "40\n";
+
+    let pair = stdout(run(&["-q", "--components"], &input));
+    for (p, betti) in [("2", "4 5 3"), ("3", "4 3 1")] {
+        assert_eq![
+            stdout(run(&["-q", "homology", "-p", p], &input)).trim(),
+            betti
+        ];
+        assert_eq![
+            stdout(run(&["-q", "homology", "--pair", "-p", p], &pair)).trim(),
+            betti
+        ];
+    }
+}
+
+// The chessboard complex of a 4 by 6 board, which takes long enough to pinch for the limits to be
+// checked while it runs
+fn chessboard() -> String {
+    stdout(run(&["-q", "generate", "chessboard", "4", "6"], ""))
+}
+
+fn read_json(path: &std::path::Path) -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn checkpoint_round_trip() {
+    let input = chessboard();
+    let betti = stdout(run(&["-q", "homology"], &input));
+    let dir = std::env::temp_dir();
+    let checkpoint = dir.join(format!["sc-simplify-checkpoint-{}", std::process::id()]);
+    let report = dir.join(format![
+        "sc-simplify-checkpoint-report-{}",
+        std::process::id()
+    ]);
+    let (checkpoint_arg, report_arg) = (checkpoint.to_str().unwrap(), report.to_str().unwrap());
+
+    // Any process uses more than a megabyte, so the run is cut short at the first check.
+    let interrupted = stdout(run(
+        &[
+            "-q",
+            "--memory-limit",
+            "1",
+            "--checkpoint",
+            checkpoint_arg,
+            "--report",
+            report_arg,
+        ],
+        &input,
+    ));
+    let json = read_json(&report);
+    assert_eq![json["interrupted"], true];
+    let stages = json["stages"].as_array().unwrap();
+    let last = stages.last().unwrap();
+    assert_eq![
+        last["cut_short_because"],
+        "the memory limit of 1MB was reached"
+    ];
+    assert![
+        std::fs::read_to_string(&checkpoint)
+            .unwrap()
+            .starts_with("sc-simplify checkpoint\n")
+    ];
+    // The run stopped before accretion, so it printed a single complex.
+    assert_eq![stdout(run(&["-q", "homology"], &interrupted)), betti];
+
+    let resumed = stdout(run(
+        &["-q", "--resume", checkpoint_arg, "--report", report_arg],
+        "",
+    ));
+    // The resumed run starts with the stage which was cut short, on the complex it left.
+    let json = read_json(&report);
+    assert_eq![json["interrupted"], false];
+    assert_eq![json["input"], last["complex"]];
+    assert_eq![json["stages"][0]["stage"], last["stage"]];
+    assert_eq![stdout(run(&["-q", "homology", "--pair"], &resumed)), betti];
+
+    std::fs::remove_file(&checkpoint).unwrap();
+    std::fs::remove_file(&report).unwrap();
+}
+
+#[test]
+fn report_schema() {
+    let input = chessboard();
+    let report = std::env::temp_dir().join(format!["sc-simplify-report-{}", std::process::id()]);
+    let report_arg = report.to_str().unwrap();
+
+    let output = stdout(run(&["-q", "--report", report_arg], &input));
+    let json = read_json(&report);
+    let keys: Vec<_> = json
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    assert_eq![
+        keys,
+        [
+            "input",
+            "interrupted",
+            "peak_memory_kib",
+            "stages",
+            "wall_time_secs"
+        ]
+    ];
+    assert_eq![
+        json["input"],
+        serde_json::json!({"vertices": 24, "facets": 360, "dimension": 3})
+    ];
+    assert_eq![json["interrupted"], false];
+
+    let stages = json["stages"].as_array().unwrap();
+    let names: Vec<_> = stages
+        .iter()
+        .map(|s| s["stage"].as_str().unwrap())
+        .collect();
+    assert_eq![names, ["nerve", "pinch", "accretion", "minimize"]];
+    assert![stages[0]["nerve_iterations"].is_u64()];
+    assert![stages[1]["pinched_edges"].is_u64()];
+    for (i, stage) in stages.iter().enumerate() {
+        assert![stage["wall_time_secs"].is_f64()];
+        assert_eq![stage["interrupted"], false];
+        assert![stage["cut_short_because"].is_null()];
+        // The subcomplex appears with accretion.
+        assert_eq![stage["subcomplex"].is_null(), i < 2];
+    }
+
+    // The last stage describes the output pair.
+    let (complex, subcomplex) = output.split_once("\n\n").unwrap();
+    let last = stages.last().unwrap();
+    assert_eq![last["complex"]["facets"], complex.lines().count()];
+    assert_eq![last["subcomplex"]["facets"], subcomplex.lines().count()];
+
+    // Memory limits are given in megabytes, so twice the peak is never reached.
+    if let Some(peak) = json["peak_memory_kib"].as_u64() {
+        let limit = (2 * peak / 1024 + 1).to_string();
+        stdout(run(
+            &["-q", "--memory-limit", &limit, "--report", report_arg],
+            &input,
+        ));
+        assert_eq![read_json(&report)["interrupted"], false];
+    }
+
+    std::fs::remove_file(&report).unwrap();
+}
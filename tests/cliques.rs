@@ -0,0 +1,52 @@
+// Checks that clique complexes of graphs have the expected facets and homotopy types.
+
+mod common;
+
+use std::fs;
+
+use common::{facets, trimmed};
+use itertools::Itertools;
+use sc_simplify::SimplicialComplex;
+use sc_simplify::io::read_edges;
+
+#[test]
+fn clique_complexes() {
+    // A cycle of length at least 4 is its own clique complex.
+    let cycle = (0..5u32).map(|v| (v, (v + 1) % 5)).collect_vec();
+    let sc = SimplicialComplex::clique_complex(cycle.iter().copied());
+    assert_eq![sc.f_vector(), vec![5, 5]];
+    assert_eq![trimmed(sc.reduced_betti_numbers(2)), vec![0, 1]];
+
+    // The complete graph gives a simplex, and the octahedron graph the boundary of the
+    // octahedron.
+    let complete = (0..6u32).tuple_combinations().collect_vec();
+    assert_eq![
+        facets(&SimplicialComplex::clique_complex(complete.iter().copied())),
+        vec![(0..6).collect_vec()]
+    ];
+    let octahedron = complete.into_iter().filter(|(v, w)| w - v != 3);
+    let sc = SimplicialComplex::clique_complex(octahedron);
+    assert_eq![sc.f_vector(), vec![6, 12, 8]];
+    assert_eq![trimmed(sc.reduced_betti_numbers(3)), vec![0, 0, 1]];
+
+    // Loops add isolated vertices, and the labels need not be consecutive.
+    let sc = SimplicialComplex::<u16>::clique_complex([(10, 20), (20, 30), (10, 30), (7, 7)]);
+    assert_eq![facets(&sc), vec![vec![7], vec![10, 20, 30]]];
+
+    let empty = SimplicialComplex::<u32>::clique_complex([]);
+    assert![empty.is_empty()];
+}
+
+#[test]
+fn edge_lists() {
+    let path = std::env::temp_dir().join(format!["sc-simplify-edges-{}", std::process::id()]);
+    fs::write(&path, "0 1\n 1   2\n\n3\n2 0\n").unwrap();
+    let edges = read_edges(Some(&path));
+    fs::remove_file(&path).unwrap();
+
+    assert_eq![edges, vec![(0, 1), (1, 2), (3, 3), (0, 2)]];
+    assert_eq![
+        facets(&SimplicialComplex::clique_complex(edges)),
+        vec![vec![0, 1, 2], vec![3]]
+    ];
+}
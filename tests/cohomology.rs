@@ -0,0 +1,111 @@
+// Checks cohomology rings over F_2, which tell apart complexes with the same Betti numbers.
+
+mod common;
+
+use common::SC;
+use itertools::Itertools;
+use sc_simplify::{Face, generators};
+
+fn complex(facets: &[&[u32]]) -> SC {
+    SC::from_check_unique(facets.iter().map(|f| Face::from_iter(f.iter().copied())))
+}
+
+// The nonzero cup products of pairs of classes of degree 1
+fn products_in_degree_one(sc: &SC) -> Vec<((usize, usize), Vec<usize>)> {
+    let cohomology = sc.cohomology();
+    let b1 = cohomology.betti_numbers().get(1).copied().unwrap_or(0);
+
+    (0..b1)
+        .cartesian_product(0..b1)
+        .map(|(i, j)| ((i, j), cohomology.cup((1, i), (1, j))))
+        .filter(|(_, product)| !product.is_empty())
+        .collect()
+}
+
+#[test]
+fn betti_numbers_agree_with_homology() {
+    for sc in [
+        generators::torus(),
+        generators::klein_bottle(),
+        generators::projective_plane(),
+        generators::complex_projective_plane(),
+        generators::lens_space(4, 1),
+        generators::dunce_hat(),
+    ] {
+        let mut betti = sc.betti_numbers(2);
+        betti.truncate(sc.cohomology().betti_numbers().len());
+        assert_eq![sc.cohomology().betti_numbers(), betti];
+    }
+}
+
+#[test]
+fn torus_and_wedge() {
+    // The wedge of two circles and a sphere has the Betti numbers of the torus.
+    let wedge = complex(&[
+        &[0, 1],
+        &[1, 2],
+        &[0, 2],
+        &[2, 3],
+        &[3, 4],
+        &[2, 4],
+        &[4, 5, 6],
+        &[4, 5, 7],
+        &[4, 6, 7],
+        &[5, 6, 7],
+    ]);
+    let torus = generators::torus();
+    assert_eq![wedge.cohomology().betti_numbers(), vec![1, 2, 1]];
+    assert_eq![torus.cohomology().betti_numbers(), vec![1, 2, 1]];
+
+    assert![products_in_degree_one(&wedge).is_empty()];
+    // The classes of degree 1 of the torus square to 0 and multiply to the class of degree 2.
+    assert_eq![
+        products_in_degree_one(&torus),
+        vec![((0, 1), vec![0]), ((1, 0), vec![0])]
+    ];
+}
+
+#[test]
+fn squares() {
+    let rp2 = generators::projective_plane();
+    assert_eq![products_in_degree_one(&rp2), vec![((0, 0), vec![0])]];
+
+    let cp2 = generators::complex_projective_plane();
+    let cohomology = cp2.cohomology();
+    assert_eq![cohomology.betti_numbers(), vec![1, 0, 1, 0, 1]];
+    assert_eq![cohomology.cup((2, 0), (2, 0)), vec![0]];
+    assert_eq![cohomology.cup((0, 0), (2, 0)), vec![0]];
+}
+
+#[test]
+fn cocycles() {
+    let torus = generators::torus();
+    let cohomology = torus.cohomology();
+    for i in 0..2 {
+        let cocycle = cohomology.cocycle(1, i);
+        assert_eq![cohomology.coordinates(1, &cocycle), vec![i]];
+
+        // Adding the coboundary of a vertex does not change the class.
+        let v = cocycle[0][0];
+        let star = torus
+            .iter()
+            .flat_map(|f| f.tuple().into_iter().map(|w| vec![v.min(w), v.max(w)]))
+            .filter(|e| e[0] != e[1] && e.contains(&v))
+            .unique()
+            .collect_vec();
+        let shifted = cocycle
+            .iter()
+            .cloned()
+            .chain(star)
+            .counts()
+            .into_iter()
+            .filter(|(_, n)| n % 2 == 1)
+            .map(|(e, _)| e)
+            .collect_vec();
+        assert_eq![cohomology.coordinates(1, &shifted), vec![i]];
+    }
+    assert_eq![
+        cohomology.coordinates(0, &(0..7).map(|v| vec![v]).collect_vec()),
+        vec![0]
+    ];
+}
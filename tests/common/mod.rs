@@ -0,0 +1,31 @@
+// Helpers shared by the integration tests, each of which only uses some of them
+#![allow(dead_code)]
+
+use sc_simplify::{SimplicialComplex, SubcomplexStrategy, Vertex};
+
+pub type SC = SimplicialComplex<u32>;
+
+pub const STRATEGIES: [SubcomplexStrategy; 5] = [
+    SubcomplexStrategy::Fifo,
+    SubcomplexStrategy::Degree,
+    SubcomplexStrategy::Neighbors,
+    SubcomplexStrategy::Overlap,
+    SubcomplexStrategy::Seeds(3),
+];
+
+// The facets as sorted tuples, in lexicographic order
+pub fn facets<Point: Vertex>(sc: &SimplicialComplex<Point>) -> Vec<Vec<Point>> {
+    let mut facets: Vec<Vec<Point>> = sc.iter().map(|f| f.tuple()).collect();
+    facets.sort_unstable();
+
+    facets
+}
+
+// Betti numbers without their trailing zeros
+pub fn trimmed(mut betti: Vec<usize>) -> Vec<usize> {
+    while betti.last() == Some(&0) {
+        betti.pop();
+    }
+
+    betti
+}
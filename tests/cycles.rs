@@ -0,0 +1,159 @@
+// Checks that the cycles of simplified complexes lift to cycles of the input which represent a
+// basis of its homology.
+
+mod common;
+
+use std::sync::atomic::AtomicBool;
+
+use common::SC;
+use itertools::Itertools;
+use sc_simplify::homology::Chain;
+use sc_simplify::progress_indicator::Silent;
+use sc_simplify::{History, generators};
+
+fn boundary(chain: &Chain<u32>, modulus: u32) -> Vec<(Vec<u32>, u32)> {
+    chain
+        .iter()
+        .filter(|(f, _)| f.len() > 1)
+        .flat_map(|(f, c)| {
+            (0..f.len()).map(move |i| {
+                let mut face = f.clone();
+                face.remove(i);
+                let c = if i % 2 == 0 { *c } else { modulus - c };
+                (face, c)
+            })
+        })
+        .into_grouping_map()
+        .fold(0, |sum, _, c| (sum + c) % modulus)
+        .into_iter()
+        .filter(|(_, c)| *c != 0)
+        .collect()
+}
+
+// The rank over F_2 of a matrix with at most 64 columns, given by its rows
+fn rank(mut rows: Vec<u64>) -> usize {
+    let mut rank = 0;
+    for bit in 0..64 {
+        if let Some(i) = (rank..rows.len()).find(|i| rows[*i] >> bit & 1 == 1) {
+            rows.swap(rank, i);
+            for j in 0..rows.len() {
+                if j != rank && rows[j] >> bit & 1 == 1 {
+                    rows[j] ^= rows[rank];
+                }
+            }
+            rank += 1;
+        }
+    }
+
+    rank
+}
+
+// Simplify with nerves and pinching, and lift the cycles of the result.
+fn lifted_cycles(sc: &SC, modulus: u32) -> Vec<Vec<Chain<u32>>> {
+    let mut simplified = sc.clone();
+    let mut history = History::default();
+    simplified.nerve_reduce_recorded(&mut history, None, &Silent);
+    while simplified.pinch_recorded(&mut history, None, &Silent) > 0 {}
+    assert![!history.is_empty()];
+
+    simplified
+        .cycles(modulus)
+        .iter()
+        .map(|cycles| cycles.iter().map(|c| history.lift(c, modulus)).collect())
+        .collect()
+}
+
+#[test]
+fn lifted_cycles_form_a_basis() {
+    for sc in [
+        generators::boolean_lattice_complex(4),
+        generators::partition_lattice_complex(4),
+        generators::klein_bottle(),
+        generators::projective_plane(),
+        generators::chessboard_complex(3, 4),
+        generators::random_flag_complex(14, 0.4, 1),
+    ] {
+        let cycles = lifted_cycles(&sc, 2);
+        let cohomology = sc.cohomology();
+        let mut betti = cohomology.betti_numbers();
+        betti.truncate(cycles.len());
+        assert_eq![cycles.iter().map(|c| c.len()).collect_vec(), betti];
+        for (d, cycles) in cycles.iter().enumerate() {
+            let faces = sc
+                .iter()
+                .flat_map(|f| f.tuple().into_iter().combinations(d + 1))
+                .collect::<std::collections::HashSet<_>>();
+            for cycle in cycles {
+                assert![cycle.iter().all(|(f, _)| faces.contains(f))];
+                assert![boundary(cycle, 2).is_empty()];
+            }
+            // The cycles form a basis if and only if pairing them with a basis of the cohomology
+            // gives an invertible matrix.
+            let pairing = (0..cycles.len())
+                .map(|i| {
+                    let cocycle = cohomology.cocycle(d, i);
+                    cycles
+                        .iter()
+                        .enumerate()
+                        .map(|(j, cycle)| {
+                            let value = cycle.iter().filter(|(f, _)| cocycle.contains(f)).count()
+                                as u64
+                                % 2;
+                            value << j
+                        })
+                        .sum()
+                })
+                .collect_vec();
+            assert_eq![rank(pairing), cycles.len()];
+        }
+    }
+}
+
+#[test]
+fn odd_characteristic() {
+    // The boundary of a 3-simplex, subdivided: its single 2-cycle lifts with coefficients ±1.
+    let sc = generators::boolean_lattice_complex(4);
+    let cycles = lifted_cycles(&sc, 5);
+    assert_eq![cycles.iter().map(|c| c.len()).collect_vec(), vec![1, 0, 1]];
+    let sphere = &cycles[2][0];
+    assert_eq![sphere.len(), sc.len()];
+    assert![sphere.iter().all(|(_, c)| *c == 1 || *c == 4)];
+    assert![boundary(sphere, 5).is_empty()];
+}
+
+#[test]
+fn recorded_nerves() {
+    for sc in [
+        generators::boolean_lattice_complex(4),
+        generators::chessboard_complex(3, 4),
+        generators::torus(),
+        generators::random_flag_complex(14, 0.4, 1),
+        // The last nerve taken is no improvement and is dropped.
+        generators::random_flag_complex(12, 0.5, 4),
+    ] {
+        let mut reduced = sc.clone();
+        let n = reduced.nerve_reduce(None, &Silent);
+        let mut recorded = sc.clone();
+        let mut history = History::default();
+        assert_eq![
+            recorded.nerve_reduce_recorded(&mut history, None, &Silent),
+            n
+        ];
+        assert![recorded == reduced];
+        for cycles in recorded.cycles(2) {
+            for cycle in cycles {
+                assert![boundary(&history.lift(&cycle, 2), 2).is_empty()];
+            }
+        }
+    }
+
+    // Nothing is recorded if the reduction is interrupted before the first nerve is taken.
+    let mut sc = generators::torus();
+    let mut history = History::default();
+    let interrupted = AtomicBool::new(true);
+    assert_eq![
+        sc.nerve_reduce_recorded(&mut history, Some(&interrupted), &Silent),
+        0
+    ];
+    assert![sc == generators::torus() && history.is_empty()];
+}
@@ -0,0 +1,125 @@
+// Checks presentations of the fundamental groups of the standard triangulations.
+
+mod common;
+
+use common::SC;
+use itertools::Itertools;
+use sc_simplify::progress_indicator::Silent;
+use sc_simplify::{Face, Presentation, generators};
+
+// The sum of the exponents of each generator in a relation
+fn exponent_sums(presentation: &Presentation, relation: &[i32]) -> Vec<i64> {
+    (1..=presentation.generator_count as i32)
+        .map(|k| {
+            relation
+                .iter()
+                .map(|a| (*a == k) as i64 - (*a == -k) as i64)
+                .sum()
+        })
+        .collect()
+}
+
+#[test]
+fn surfaces() {
+    for sc in [generators::torus(), generators::klein_bottle()] {
+        let presentation = sc.fundamental_group();
+        assert_eq![presentation.generator_count, 2];
+        assert_eq![presentation.relations.len(), 1];
+        assert_eq![presentation.relations[0].len(), 4];
+    }
+    // The relation of the torus is a commutator, so the group is abelian.
+    let torus = generators::torus().fundamental_group();
+    assert_eq![torus.relations, vec![vec![1, 2, -1, -2]]];
+
+    let rp2 = generators::projective_plane().fundamental_group();
+    assert_eq![
+        rp2,
+        Presentation {
+            generator_count: 1,
+            relations: vec![vec![1, 1]]
+        }
+    ];
+}
+
+#[test]
+fn cyclic_groups() {
+    for (p, q) in [(3, 1), (5, 2), (7, 3)] {
+        let mut sc = generators::lens_space(p, q);
+        while sc.pinch(None, &Silent) > 0 {}
+        let presentation = sc.fundamental_group();
+        assert_eq![presentation.generator_count, 1];
+        assert_eq![presentation.relations, vec![vec![1; p as usize]]];
+    }
+}
+
+#[test]
+fn simply_connected() {
+    for sc in [
+        generators::sphere(2),
+        generators::sphere(3),
+        generators::dunce_hat(),
+        generators::bings_house(),
+        generators::complex_projective_plane(),
+    ] {
+        assert![sc.fundamental_group().is_trivial()];
+    }
+    assert![SC::default().fundamental_group().is_trivial()];
+}
+
+#[test]
+fn graphs() {
+    // The 1-skeleton of a tetrahedron and an isolated vertex: only the component of the least
+    // vertex counts.
+    let sc = SC::from_check_unique(
+        (0..4)
+            .tuple_combinations()
+            .map(|(v, w)| Face::from_iter([v, w]))
+            .chain([Face::from_iter([9])]),
+    );
+    let presentation = sc.fundamental_group();
+    assert_eq![presentation.generator_count, 3];
+    assert![presentation.relations.is_empty()];
+}
+
+#[test]
+fn poincare_sphere() {
+    let mut sc = generators::poincare_sphere();
+    while sc.pinch(None, &Silent) > 0 {}
+    let presentation = sc.fundamental_group();
+    assert![!presentation.is_trivial()];
+    assert_eq![presentation.generator_count, 2];
+    // The binary icosahedral group is perfect, so the exponent sums of the relations span Z^2.
+    let sums = presentation
+        .relations
+        .iter()
+        .map(|r| exponent_sums(&presentation, r))
+        .collect_vec();
+    let gcd = sums
+        .iter()
+        .tuple_combinations()
+        .map(|(a, b)| (a[0] * b[1] - a[1] * b[0]).abs())
+        .fold(0, |g, d| {
+            let (mut g, mut d) = (g, d);
+            while d != 0 {
+                (g, d) = (d, g % d);
+            }
+            g
+        });
+    assert_eq![gcd, 1];
+}
+
+#[test]
+fn tietze_transformations() {
+    let mut presentation = Presentation {
+        generator_count: 3,
+        relations: vec![vec![1, 2, -1], vec![3, 3, -3, 1, -1], vec![]],
+    };
+    presentation.simplify();
+    assert_eq![
+        presentation,
+        Presentation {
+            generator_count: 1,
+            relations: Vec::new()
+        }
+    ];
+}
@@ -0,0 +1,308 @@
+// Checks that the standard triangulations have the expected invariants, and that the
+// simplifications preserve them on these complexes.
+
+mod common;
+
+use std::collections::HashMap;
+
+use common::{SC, facets, trimmed};
+use itertools::Itertools;
+use sc_simplify::generators::*;
+use sc_simplify::progress_indicator::Silent;
+use sc_simplify::{partial_bijection_complex, partial_bijection_facets};
+
+fn reduced_betti(sc: &SC, p: u32) -> Vec<usize> {
+    trimmed(sc.reduced_betti_numbers(p))
+}
+
+// The number of facets containing each face of codimension 1
+fn ridge_degrees(sc: &SC) -> HashMap<Vec<u32>, usize> {
+    sc.iter()
+        .flat_map(|f| f.tuple().into_iter().combinations(f.len() - 1))
+        .counts()
+}
+
+// Every face of codimension 1 of a closed pseudomanifold lies in exactly two facets.
+fn assert_closed_pseudomanifold(sc: &SC) {
+    assert![sc.is_pure()];
+    assert![ridge_degrees(sc).values().all(|d| *d == 2)];
+}
+
+// A complex with no face of codimension 1 in a single facet has no elementary collapse.
+fn assert_no_free_faces(sc: &SC) {
+    assert![ridge_degrees(sc).values().all(|d| *d >= 2)];
+}
+
+fn simplified(sc: &SC) -> SC {
+    let mut sc = sc.clone();
+    sc.nerve_reduce(None, &Silent);
+    while sc.pinch(None, &Silent) > 0 {}
+
+    sc
+}
+
+#[test]
+fn spheres() {
+    for dim in 0..5 {
+        let sc = sphere(dim);
+        assert_closed_pseudomanifold(&sc);
+        assert_eq![sc.f_vector()[0], dim as u128 + 2];
+        let mut betti = vec![0; dim as usize];
+        betti.push(1);
+        assert_eq![reduced_betti(&sc, 2), betti];
+        assert_eq![reduced_betti(&simplified(&sc), 3), betti];
+    }
+}
+
+#[test]
+fn surfaces() {
+    let torus = torus();
+    assert_eq![torus.f_vector(), vec![7, 21, 14]];
+    assert_closed_pseudomanifold(&torus);
+    assert_eq![reduced_betti(&torus, 3), vec![0, 2, 1]];
+
+    let projective_plane = projective_plane();
+    assert_eq![projective_plane.f_vector(), vec![6, 15, 10]];
+    assert_closed_pseudomanifold(&projective_plane);
+    assert_eq![reduced_betti(&projective_plane, 2), vec![0, 1, 1]];
+    assert_eq![reduced_betti(&projective_plane, 3), vec![]];
+
+    let klein_bottle = klein_bottle();
+    assert_eq![klein_bottle.f_vector(), vec![9, 27, 18]];
+    assert_closed_pseudomanifold(&klein_bottle);
+    assert_eq![reduced_betti(&klein_bottle, 2), vec![0, 2, 1]];
+    assert_eq![reduced_betti(&klein_bottle, 3), vec![0, 1]];
+}
+
+#[test]
+fn complex_projective_plane_is_3_neighborly() {
+    let sc = complex_projective_plane();
+    assert_eq![sc.f_vector(), vec![9, 36, 84, 90, 36]];
+    assert_closed_pseudomanifold(&sc);
+    for p in [2, 3] {
+        assert_eq![reduced_betti(&sc, p), vec![0, 0, 1, 0, 1]];
+    }
+}
+
+#[test]
+fn lens_spaces() {
+    for (p, q) in [(2, 1), (3, 1), (5, 2), (7, 3)] {
+        let sc = lens_space(p, q);
+        assert_closed_pseudomanifold(&sc);
+        assert_eq![sc.euler_characteristic(), 0];
+        // The first homology group is cyclic of order p.
+        for prime in [2, 3, 5, 7] {
+            let betti = if p == prime {
+                vec![0, 1, 1, 1]
+            } else {
+                vec![0, 0, 0, 1]
+            };
+            assert_eq![reduced_betti(&simplified(&sc), prime), betti];
+        }
+    }
+}
+
+#[test]
+fn poincare_sphere_is_a_homology_sphere() {
+    let sc = poincare_sphere();
+    assert_closed_pseudomanifold(&sc);
+    assert_eq![sc.euler_characteristic(), 0];
+    let simplified = simplified(&sc);
+    for p in [2, 3, 5] {
+        assert_eq![reduced_betti(&simplified, p), vec![0, 0, 0, 1]];
+    }
+}
+
+#[test]
+fn contractible_complexes_are_not_collapsible() {
+    let dunce_hat = dunce_hat();
+    assert_eq![dunce_hat.f_vector(), vec![8, 24, 17]];
+    let bings_house = bings_house();
+    for sc in [dunce_hat, bings_house] {
+        assert_no_free_faces(&sc);
+        assert_eq![sc.euler_characteristic(), 1];
+        for p in [2, 3] {
+            assert_eq![reduced_betti(&sc, p), vec![]];
+            assert_eq![reduced_betti(&simplified(&sc), p), vec![]];
+        }
+    }
+}
+
+#[test]
+fn random_complexes_depend_only_on_their_seed() {
+    for seed in 0..5 {
+        assert_eq![
+            facets(&linial_meshulam_complex(9, 2, 0.3, seed)),
+            facets(&linial_meshulam_complex(9, 2, 0.3, seed))
+        ];
+        assert_eq![
+            facets(&random_flag_complex(12, 0.4, seed)),
+            facets(&random_flag_complex(12, 0.4, seed))
+        ];
+        assert_eq![
+            facets(&random_facet_complex(12, 10, 4, seed)),
+            facets(&random_facet_complex(12, 10, 4, seed))
+        ];
+    }
+    assert_ne![
+        facets(&random_flag_complex(12, 0.4, 0)),
+        facets(&random_flag_complex(12, 0.4, 1))
+    ];
+}
+
+#[test]
+fn linial_meshulam_complexes_have_full_skeleta() {
+    for seed in 0..20 {
+        let sc = linial_meshulam_complex(8, 2, 0.3, seed);
+        let f = sc.f_vector();
+        assert_eq![f[..2], [8, 28]];
+        assert![sc.iter().all(|f| f.len() == 2 || f.len() == 3)];
+    }
+    assert_eq![
+        linial_meshulam_complex(6, 3, 0.0, 0).f_vector(),
+        vec![6, 15, 20]
+    ];
+    assert_eq![
+        facets(&linial_meshulam_complex(4, 3, 1.0, 0)),
+        vec![vec![0, 1, 2, 3]]
+    ];
+}
+
+#[test]
+fn random_flag_complexes_are_clique_complexes() {
+    for seed in 0..20 {
+        let sc = random_flag_complex(10, 0.5, seed);
+        let mut adjacent = [0u32; 10];
+        for f in sc.iter() {
+            for (v, w) in f.iter().tuple_combinations() {
+                adjacent[*v as usize] |= 1 << w;
+                adjacent[*w as usize] |= 1 << v;
+            }
+        }
+        let is_clique =
+            |s: u32| (0..10).all(|v| s & (1 << v) == 0 || s & !adjacent[v] & !(1 << v) == 0);
+        let maximal_cliques = (1..1u32 << 10)
+            .filter(|s| {
+                is_clique(*s) && (0..10).all(|v| s & (1 << v) != 0 || !is_clique(s | 1 << v))
+            })
+            .map(|s| (0..10).filter(|v| s & (1 << v) != 0).collect_vec())
+            .sorted()
+            .collect_vec();
+        assert_eq![facets(&sc), maximal_cliques];
+    }
+}
+
+#[test]
+fn random_facets_have_the_given_size() {
+    for seed in 0..20 {
+        let sc = random_facet_complex(10, 8, 4, seed);
+        assert![sc.len() <= 8];
+        assert![sc.iter().all(|f| f.len() == 4 && f.iter().all(|v| *v < 10))];
+    }
+    assert_eq![
+        facets(&random_facet_complex(5, 3, 5, 0)),
+        vec![vec![0, 1, 2, 3, 4]]
+    ];
+}
+
+// A wedge of `count` spheres of dimension `dim`, as trimmed reduced Betti numbers
+fn wedge(count: usize, dim: usize) -> Vec<usize> {
+    let mut betti = vec![0; dim];
+    betti.push(count);
+
+    betti
+}
+
+#[test]
+fn matching_complexes() {
+    let complete = |n: u32| (0..n).tuple_combinations().collect_vec();
+    // The Petersen graph
+    assert_eq![
+        reduced_betti(&matching_complex(&complete(5)), 2),
+        wedge(6, 1)
+    ];
+    // The first homology group of the matching complex of K_7 is cyclic of order 3.
+    let sc = matching_complex(&complete(7));
+    assert_eq![reduced_betti(&sc, 2), wedge(20, 2)];
+    assert_eq![reduced_betti(&sc, 3), vec![0, 1, 21]];
+
+    assert_eq![reduced_betti(&chessboard_complex(2, 3), 2), wedge(1, 1)];
+    // The chessboard complex M_{3,4} is a torus.
+    for sc in [chessboard_complex(3, 4), chessboard_complex(4, 3)] {
+        assert_eq![sc.f_vector(), vec![12, 36, 24]];
+        assert_closed_pseudomanifold(&sc);
+        assert_eq![reduced_betti(&sc, 3), vec![0, 2, 1]];
+    }
+}
+
+#[test]
+fn independence_complexes_of_cycles() {
+    // Kozlov's computation of the homotopy types of the independence complexes of cycles
+    for n in 3..12 {
+        let cycle = (0..n).map(|v| (v, (v + 1) % n)).collect_vec();
+        let k = n as usize / 3;
+        let betti = match n % 3 {
+            0 => wedge(2, k - 1),
+            1 => wedge(1, k - 1),
+            _ => wedge(1, k),
+        };
+        assert_eq![reduced_betti(&independence_complex(n, &cycle), 2), betti];
+    }
+}
+
+#[test]
+fn complexes_of_not_k_connected_graphs() {
+    // Disconnected graphs give a wedge of (n - 1)! spheres of dimension n - 3, and graphs which
+    // are not 2-connected a wedge of (n - 2)! spheres of dimension 2n - 5.
+    assert_eq![
+        reduced_betti(&not_k_connected_complex(4, 1), 2),
+        wedge(6, 1)
+    ];
+    assert_eq![
+        reduced_betti(&not_k_connected_complex(5, 1), 3),
+        wedge(24, 2)
+    ];
+    assert_eq![
+        reduced_betti(&not_k_connected_complex(4, 2), 2),
+        wedge(2, 3)
+    ];
+    assert_eq![
+        reduced_betti(&not_k_connected_complex(5, 2), 2),
+        wedge(6, 5)
+    ];
+}
+
+#[test]
+fn order_complexes_of_lattices() {
+    for n in 2..6 {
+        let sc = boolean_lattice_complex(n);
+        assert_eq![sc.f_vector()[0], (1 << n) - 2];
+        assert_closed_pseudomanifold(&sc);
+        assert_eq![reduced_betti(&sc, 2), wedge(1, n as usize - 2)];
+    }
+    // The proper part of the partition lattice is a wedge of (n - 1)! spheres of dimension n - 3.
+    assert_eq![reduced_betti(&partition_lattice_complex(4), 2), wedge(6, 1)];
+    let sc = partition_lattice_complex(5);
+    assert_eq![sc.f_vector()[0], 50];
+    assert_eq![reduced_betti(&sc, 3), wedge(24, 2)];
+}
+
+#[test]
+fn partial_bijection_complexes() {
+    // The f-vectors and homology of the complexes built before facets were streamed
+    for (a, b, f, betti) in [
+        (1, 3, vec![3], wedge(2, 0)),
+        (2, 3, vec![12, 12], wedge(1, 1)),
+        (3, 3, vec![15, 18], wedge(4, 1)),
+        (3, 4, vec![72, 216, 144], vec![0, 2, 1]),
+        (4, 4, vec![112, 384, 288], wedge(15, 2)),
+    ] {
+        let sc = partial_bijection_complex(a, b);
+        assert_eq![sc.f_vector(), f];
+        assert_eq![reduced_betti(&sc, 2), betti];
+        // The vertices are numbered consecutively, and each facet is generated once.
+        assert_eq![sc.iter().flatten().max(), Some(&(f[0] as u32 - 1))];
+        assert_eq![partial_bijection_facets(a, b).count(), sc.len()];
+        assert_eq![partial_bijection_complex(b, a).f_vector(), sc.f_vector()];
+    }
+}
@@ -0,0 +1,254 @@
+// Property tests for the operations of `sc-simplify`: each one should preserve the homotopy type
+// of a complex, or of the pair it produces. Homotopy types are compared through their Euler
+// characteristics and Betti numbers over F_2, on random complexes and standard triangulations.
+// Acyclic accretion only preserves homology over its own field, so it is checked over that field.
+
+mod common;
+
+use common::{SC, STRATEGIES, trimmed};
+use sc_simplify::Face;
+use sc_simplify::generators::{dunce_hat, klein_bottle, projective_plane, sphere, torus};
+use sc_simplify::progress_indicator::Silent;
+
+const CASES: usize = 200;
+
+// A small xorshift generator, so that failures are reproducible from the seed alone
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+
+        self.0
+    }
+
+    // A number in `lo..hi`
+    fn range(&mut self, lo: u64, hi: u64) -> u64 {
+        lo + self.next() % (hi - lo)
+    }
+
+    fn chance(&mut self, p: f64) -> bool {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64 <= p
+    }
+}
+
+fn complex(facets: &[Vec<u32>]) -> SC {
+    SC::from_check_unique(facets.iter().map(|f| Face::from_iter(f.iter().copied())))
+}
+
+// Random facets with up to 4 vertices on up to 9 vertices
+fn random_facets(rng: &mut Rng) -> SC {
+    let n = rng.range(3, 10) as u32;
+    let facets: Vec<Vec<u32>> = (0..rng.range(2, 14))
+        .map(|_| {
+            (0..rng.range(1, 5))
+                .map(|_| rng.range(0, n as u64) as u32)
+                .collect()
+        })
+        .collect();
+
+    complex(&facets)
+}
+
+// The clique complex of a random graph on up to 9 vertices
+fn random_flag_complex(rng: &mut Rng) -> SC {
+    let n = rng.range(3, 10) as usize;
+    let p = rng.range(30, 80) as f64 / 100.0;
+    let mut adjacent = vec![0u32; n];
+    for v in 0..n {
+        for w in 0..v {
+            if rng.chance(p) {
+                adjacent[v] |= 1 << w;
+                adjacent[w] |= 1 << v;
+            }
+        }
+    }
+    // Every vertex is a clique, so every vertex appears in some facet.
+    let cliques: Vec<Vec<u32>> = (1..1u32 << n)
+        .filter(|s| (0..n).all(|v| s & (1 << v) == 0 || s & !(1 << v) & !adjacent[v] == 0))
+        .map(|s| (0..n as u32).filter(|v| s & (1 << v) != 0).collect())
+        .collect();
+
+    complex(&cliques)
+}
+
+// The complexes every property is checked on
+fn samples() -> Vec<SC> {
+    let mut rng = Rng::new(0x5C5);
+    let mut samples = vec![
+        sphere(1),
+        sphere(2),
+        sphere(3),
+        torus(),
+        projective_plane(),
+        klein_bottle(),
+        dunce_hat(),
+    ];
+    for _ in 0..CASES {
+        samples.push(random_facets(&mut rng));
+        samples.push(random_flag_complex(&mut rng));
+    }
+    samples.iter().for_each(assert_maximal);
+
+    samples
+}
+
+fn reduced_betti(sc: &SC, p: u32) -> Vec<usize> {
+    trimmed(sc.reduced_betti_numbers(p))
+}
+
+// The reduced Betti numbers of the quotient of `sc` by the non-empty subcomplex `sub`
+fn relative_betti(sc: &SC, sub: &SC, p: u32) -> Vec<usize> {
+    trimmed(sc.relative_betti_numbers(sub, p))
+}
+
+fn union(sc: &SC, sub: &SC) -> SC {
+    SC::from_check_unique(sc.iter().chain(sub).cloned())
+}
+
+fn assert_maximal(sc: &SC) {
+    for (i, f) in sc.iter().enumerate() {
+        for (j, g) in sc.iter().enumerate() {
+            assert![i == j || !f.leq(g), "{f:?} is not a facet of {sc:?}"];
+        }
+    }
+}
+
+fn assert_subcomplex(sub: &SC, sc: &SC) {
+    for f in sub {
+        assert![sc.has_face(f), "{f:?} is not a face of {sc:?}"];
+    }
+}
+
+fn assert_equivalent(before: &SC, after: &SC) {
+    assert_eq![before.euler_characteristic(), after.euler_characteristic()];
+    assert_eq![reduced_betti(before, 2), reduced_betti(after, 2)];
+}
+
+// The accreted subcomplex is acyclic over F_p, so the quotient by it has the homology of the
+// original complex over F_p, and all of it when the subcomplex is contractible.
+fn assert_pair_equivalent(before: &SC, sc: &SC, sub: &SC, p: u32) {
+    let sc = union(sc, sub);
+    assert_eq![
+        before.euler_characteristic(),
+        sc.euler_characteristic() - sub.euler_characteristic() + 1
+    ];
+    assert_eq![reduced_betti(before, p), relative_betti(&sc, sub, p)];
+}
+
+#[test]
+fn nerve_preserves_homotopy_type() {
+    for sc in samples() {
+        let nerve = sc.nerve();
+        assert_maximal(&nerve);
+        assert_equivalent(&sc, &nerve);
+    }
+}
+
+#[test]
+fn nerve_reduce_preserves_homotopy_type() {
+    for sc in samples() {
+        let mut reduced = sc.clone();
+        reduced.nerve_reduce(None, &Silent);
+        assert_maximal(&reduced);
+        assert_equivalent(&sc, &reduced);
+    }
+}
+
+#[test]
+fn pinch_preserves_homotopy_type() {
+    for sc in samples() {
+        let mut pinched = sc.clone();
+        while pinched.pinch(None, &Silent) > 0 {}
+        assert_maximal(&pinched);
+        assert_equivalent(&sc, &pinched);
+    }
+}
+
+#[test]
+fn contractible_subcomplex_is_contractible() {
+    for sc in samples() {
+        for strategy in STRATEGIES {
+            let mut rest = sc.clone();
+            let sub = rest.contractible_subcomplex(strategy, None, &Silent);
+            assert_maximal(&sub);
+            assert_subcomplex(&sub, &sc);
+            assert_subcomplex(&rest, &sc);
+            assert_eq![sub.euler_characteristic(), 1];
+            assert![reduced_betti(&sub, 2).is_empty()];
+            assert_pair_equivalent(&sc, &rest, &sub, 2);
+        }
+    }
+}
+
+#[test]
+fn minimize_pair_preserves_homotopy_type() {
+    for sc in samples() {
+        for strategy in STRATEGIES {
+            let mut rest = sc.clone();
+            let contractible = rest.contractible_subcomplex(strategy, None, &Silent);
+            let sub = rest
+                .minimize_pair(&contractible, None, &Silent)
+                .expect("Minimization should not be interrupted");
+            assert_maximal(&rest);
+            assert_maximal(&sub);
+            assert_subcomplex(&sub, &union(&rest, &contractible));
+            assert_pair_equivalent(&sc, &rest, &sub, 2);
+        }
+    }
+}
+
+#[test]
+fn acyclic_subcomplex_is_acyclic() {
+    for sc in samples() {
+        for strategy in STRATEGIES {
+            for p in [2, 3] {
+                let mut rest = sc.clone();
+                let sub = rest.acyclic_subcomplex(strategy, p, None, &Silent);
+                assert_maximal(&sub);
+                assert_subcomplex(&sub, &sc);
+                assert_subcomplex(&rest, &sc);
+                assert_eq![sub.euler_characteristic(), 1];
+                assert![reduced_betti(&sub, p).is_empty()];
+                assert_pair_equivalent(&sc, &rest, &sub, p);
+            }
+        }
+    }
+}
+
+#[test]
+fn acyclic_accretion_goes_past_contractible_accretion() {
+    // The join of the projective plane with a tetrahedron is contractible and is accreted first,
+    // as its facets are the largest. The simplex on the vertices of the projective plane meets it
+    // in the projective plane, which is acyclic over F_3 but not over F_2, so over F_3 the whole
+    // complex is accreted.
+    let rp2 = projective_plane();
+    let sc = SC::from_check_unique(
+        rp2.iter()
+            .map(|f| f.iter().copied().chain(6..10).collect())
+            .chain([Face::from_iter(0..6)]),
+    );
+    assert_eq![reduced_betti(&sc, 2), [0, 0, 1, 1]];
+    assert![reduced_betti(&sc, 3).is_empty()];
+
+    for strategy in STRATEGIES {
+        let mut contractible_rest = sc.clone();
+        let contractible = contractible_rest.contractible_subcomplex(strategy, None, &Silent);
+        assert_eq![(contractible_rest.len(), contractible.len()), (1, 10)];
+
+        let mut rest = sc.clone();
+        let sub = rest.acyclic_subcomplex(strategy, 2, None, &Silent);
+        assert_eq![(rest.len(), sub.len()), (1, 10)];
+
+        let mut rest = sc.clone();
+        let sub = rest.acyclic_subcomplex(strategy, 3, None, &Silent);
+        assert_eq![(rest.len(), sub.len()), (0, 11)];
+        assert_pair_equivalent(&sc, &rest, &sub, 3);
+    }
+}
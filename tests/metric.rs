@@ -0,0 +1,92 @@
+// Checks the Vietoris–Rips and Čech complexes of small point clouds and distance matrices.
+
+mod common;
+
+use std::f64::consts::PI;
+use std::fs;
+
+use common::{SC, trimmed};
+use sc_simplify::io::{read_distance_matrix, read_table};
+
+fn euclidean(points: &[Vec<f64>]) -> impl Fn(usize, usize) -> f64 + Sync + '_ {
+    |i, j| {
+        points[i]
+            .iter()
+            .zip(&points[j])
+            .map(|(x, y)| (x - y) * (x - y))
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+#[test]
+fn square() {
+    let square = vec![
+        vec![0.0, 0.0],
+        vec![1.0, 0.0],
+        vec![1.0, 1.0],
+        vec![0.0, 1.0],
+    ];
+    let d = euclidean(&square);
+
+    assert_eq![SC::vietoris_rips(4, &d, 0.5).f_vector(), vec![4]];
+    let cycle = SC::vietoris_rips(4, &d, 1.0);
+    assert_eq![cycle.f_vector(), vec![4, 4]];
+    assert_eq![trimmed(cycle.reduced_betti_numbers(2)), vec![0, 1]];
+    assert_eq![SC::vietoris_rips(4, &d, 1.5).f_vector(), vec![4, 6, 4, 1]];
+
+    // Each ball of radius 1 around a corner holds the corner and its two neighbors, and these
+    // four triangles make up the boundary of a tetrahedron.
+    assert_eq![SC::cech(4, &d, 0.5).f_vector(), vec![4]];
+    let sphere = SC::cech(4, &d, 1.0);
+    assert_eq![sphere.f_vector(), vec![4, 6, 4]];
+    assert_eq![trimmed(sphere.reduced_betti_numbers(2)), vec![0, 0, 1]];
+    assert_eq![SC::cech(4, &d, 1.5).f_vector(), vec![4, 6, 4, 1]];
+}
+
+#[test]
+fn samples_of_a_circle() {
+    let points: Vec<Vec<f64>> = (0..12)
+        .map(|k| {
+            let t = 2.0 * PI * k as f64 / 12.0;
+            vec![t.cos(), t.sin()]
+        })
+        .collect();
+    let d = euclidean(&points);
+    // Neighbors are about 0.52 apart, and opposite points 2 apart.
+    for scale in [0.6, 1.1] {
+        assert_eq![
+            trimmed(SC::vietoris_rips(12, &d, scale).reduced_betti_numbers(2)),
+            vec![0, 1]
+        ];
+        assert_eq![
+            trimmed(SC::cech(12, &d, scale).reduced_betti_numbers(2)),
+            vec![0, 1]
+        ];
+    }
+    assert_eq![
+        trimmed(SC::vietoris_rips(12, &d, 0.4).reduced_betti_numbers(2)),
+        vec![11]
+    ];
+    assert_eq![SC::cech(12, &d, 2.0).f_vector()[0], 12];
+    assert_eq![SC::cech(12, &d, 2.0).len(), 1];
+}
+
+#[test]
+fn tables() {
+    let dir = std::env::temp_dir();
+    let points = dir.join(format!["sc-simplify-points-{}", std::process::id()]);
+    fs::write(&points, "x,y\n0,0\n\n1.5, 2\n-1 3e-1\n").unwrap();
+    let matrix = dir.join(format!["sc-simplify-matrix-{}", std::process::id()]);
+    fs::write(&matrix, "0 1 2\n1 0 1\n2 1 0\n").unwrap();
+
+    assert_eq![
+        read_table(Some(&points)),
+        vec![vec![0.0, 0.0], vec![1.5, 2.0], vec![-1.0, 0.3]]
+    ];
+    let distances = read_distance_matrix(Some(&matrix));
+    let path = SC::vietoris_rips(3, |i, j| distances[i][j], 1.0);
+    assert_eq![path.f_vector(), vec![3, 2]];
+    fs::remove_file(&points).unwrap();
+    fs::remove_file(&matrix).unwrap();
+}
@@ -0,0 +1,16 @@
+// Regression tests for Čech nerves.
+
+mod common;
+
+use common::SC;
+use sc_simplify::Face;
+
+#[test]
+fn no_duplicate_facets() {
+    // The two vertices of each edge lie in the same facets, so they give the same face of the
+    // nerve, which used to be listed twice.
+    let sc = SC::from_check_unique([Face::from_iter([0, 1]), Face::from_iter([2, 3])]);
+    let nerve = sc.nerve();
+    assert_eq![nerve.len(), 2];
+    assert_eq![nerve.euler_characteristic(), 2];
+}
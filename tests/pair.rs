@@ -0,0 +1,51 @@
+// Regression tests for the iterative reduction of pairs.
+
+mod common;
+
+use common::{SC, STRATEGIES, facets};
+use sc_simplify::Face;
+use sc_simplify::progress_indicator::Silent;
+
+#[test]
+fn reduce_pair_never_grows() {
+    // A round of pinching, accretion and excision turns this pair of 5 and 1 facets into one of 3
+    // and 4 facets, which used to be kept.
+    let sc = SC::from_check_unique(
+        [
+            vec![1, 2, 4],
+            vec![0, 1, 3],
+            vec![2, 3],
+            vec![3, 4],
+            vec![0, 4],
+        ]
+        .into_iter()
+        .map(Face::from_iter),
+    );
+    let sub = SC::from_check_unique([Face::from_iter([1])]);
+
+    let (mut reduced, mut reduced_sub) = (sc.clone(), sub.clone());
+    let rounds = reduced.reduce_pair(&mut reduced_sub, None, None, &Silent);
+    assert_eq![rounds, 0];
+    assert_eq![facets(&reduced), facets(&sc)];
+    assert_eq![facets(&reduced_sub), facets(&sub)];
+}
+
+#[test]
+fn reduce_pair_of_contractible_complexes() {
+    // Accretion takes the whole of a contractible complex and leaves no facets at all, which
+    // `reduce_pair` used to index into. With `--components`, each isolated vertex or edge is
+    // reduced this way.
+    for sc in [
+        SC::from_check_unique([Face::from_iter([50])]),
+        SC::from_check_unique([Face::from_iter([40, 41])]),
+        SC::from_check_unique([Face::from_iter([0, 1, 2]), Face::from_iter([2, 3])]),
+    ] {
+        for strategy in STRATEGIES {
+            let mut rest = sc.clone();
+            let contractible = rest.contractible_subcomplex(strategy, None, &Silent);
+            let mut sub = rest.minimize_pair(&contractible, None, &Silent).unwrap();
+            assert_eq![rest.reduce_pair(&mut sub, None, None, &Silent), 0];
+            assert![rest.is_empty() && sub.is_empty()];
+        }
+    }
+}
@@ -0,0 +1,129 @@
+// Checks barcodes of small filtrations, with and without collapses.
+
+use std::f64::consts::PI;
+use std::fs;
+
+use itertools::Itertools;
+use sc_simplify::homology::{Bar, FilteredComplex};
+use sc_simplify::io::read_filtration;
+use sc_simplify::{Face, SimplicialComplex, generators};
+
+fn bars(barcode: &[Bar]) -> Vec<(usize, f64, f64)> {
+    barcode
+        .iter()
+        .map(|b| (b.dimension, b.birth, b.death))
+        .collect()
+}
+
+// The Vietoris–Rips filtration of `n` points up to `scale`
+fn rips<F>(n: usize, distance: F, scale: f64) -> FilteredComplex<u32>
+where
+    F: Fn(usize, usize) -> f64 + Sync,
+{
+    let sc = SimplicialComplex::vietoris_rips(n, &distance, scale);
+    FilteredComplex::from_times(&sc, |f| {
+        f.iter()
+            .tuple_combinations()
+            .map(|(v, w)| distance(*v as usize, *w as usize))
+            .fold(0.0, f64::max)
+    })
+}
+
+#[test]
+fn filling_a_triangle() {
+    let facets = [
+        (vec![0, 1], 0.0),
+        (vec![1, 2], 1.0),
+        (vec![0, 2], 2.0),
+        (vec![0, 1, 2], 3.0),
+        (vec![3, 4], 0.5),
+    ];
+    let filtration = FilteredComplex::from_facets(facets.map(|(f, t)| (Face::from_iter(f), t)));
+    assert_eq![filtration.len(), 10];
+    let expected = vec![
+        (0, 0.0, f64::INFINITY),
+        (0, 0.5, f64::INFINITY),
+        (1, 2.0, 3.0),
+    ];
+    assert_eq![bars(&filtration.barcode(2)), expected];
+    assert_eq![bars(&filtration.barcode(3)), expected];
+
+    // Only the vertex 4 and the edge to it enter together with the vertex free. Vertex 2 enters
+    // with the edge to 1, but lies in another edge.
+    let mut collapsed = filtration.clone();
+    assert_eq![collapsed.collapse(), 1];
+    assert_eq![bars(&collapsed.barcode(2)), expected];
+}
+
+#[test]
+fn complexes_at_a_single_time() {
+    // Without times, the infinite bars are the Betti numbers.
+    for (sc, betti) in [
+        (generators::torus(), vec![1, 2, 1]),
+        (generators::dunce_hat(), vec![1]),
+        (generators::projective_plane(), vec![1, 1, 1]),
+    ] {
+        let mut filtration = FilteredComplex::from_facets(sc.iter().map(|f| (f.clone(), 0.0)));
+        for modulus in [2, 3] {
+            let barcode = filtration.barcode(modulus);
+            assert![barcode.iter().all(|b| b.death == f64::INFINITY)];
+            let counts = barcode.iter().counts_by(|b| b.dimension);
+            let expected = if modulus == 2 { &betti } else { &betti[..1] };
+            assert_eq![
+                (0..expected.len())
+                    .map(|k| counts.get(&k).copied().unwrap_or(0))
+                    .collect_vec(),
+                *expected
+            ];
+        }
+        filtration.collapse();
+        assert![filtration.len() >= betti.iter().sum()];
+    }
+}
+
+#[test]
+fn samples_of_a_circle() {
+    let points: Vec<(f64, f64)> = (0..12)
+        .map(|k| {
+            let t = 2.0 * PI * k as f64 / 12.0;
+            (t.cos(), t.sin())
+        })
+        .collect();
+    let d = |i: usize, j: usize| {
+        ((points[i].0 - points[j].0).powi(2) + (points[i].1 - points[j].1).powi(2)).sqrt()
+    };
+    let mut filtration = rips(12, d, 2.1);
+    let barcode = filtration.barcode(2);
+    assert![filtration.collapse() > 0];
+    assert_eq![bars(&filtration.barcode(2)), bars(&barcode)];
+
+    // The points join up when neighbors, about 0.52 apart, are joined, and the circle is filled
+    // once points 4 steps apart, about 1.73 apart, are joined.
+    let neighbors = 2.0 * (PI / 12.0).sin();
+    let long = barcode
+        .iter()
+        .filter(|b| b.death - b.birth > 1.0)
+        .collect_vec();
+    assert_eq![long.len(), 2];
+    assert_eq![(long[0].dimension, long[0].death), (0, f64::INFINITY)];
+    assert_eq![long[1].dimension, 1];
+    assert![(long[1].birth - neighbors).abs() < 1e-9];
+    assert![(long[1].death - 3f64.sqrt()).abs() < 1e-9];
+    assert_eq![barcode.iter().filter(|b| b.dimension == 0).count(), 12];
+}
+
+#[test]
+fn timed_facets() {
+    let path = std::env::temp_dir().join(format!["sc-simplify-filtration-{}", std::process::id()]);
+    fs::write(&path, "0 1 @ 0.5\n\n1 2 @2\n3\n").unwrap();
+
+    assert_eq![
+        read_filtration(Some(&path)),
+        vec![
+            (Face::from_iter([0, 1]), 0.5),
+            (Face::from_iter([1, 2]), 2.0),
+            (Face::from_iter([3]), 0.0),
+        ]
+    ];
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,29 @@
+// Regression tests for pinching edges.
+
+mod common;
+
+use common::SC;
+use sc_simplify::Face;
+use sc_simplify::progress_indicator::Silent;
+
+// Whether no facet is contained in another
+fn is_maximal(sc: &SC) -> bool {
+    let facets: Vec<Vec<u32>> = sc.iter().map(|f| f.tuple()).collect();
+
+    facets.iter().enumerate().all(|(i, f)| {
+        facets
+            .iter()
+            .enumerate()
+            .all(|(j, g)| i == j || !f.iter().all(|v| g.contains(v)))
+    })
+}
+
+#[test]
+fn facets_stay_maximal() {
+    // Pinching an edge of the path 0 - 2 - 1 used to leave the vertex 1 as a facet next to the
+    // edge containing it.
+    let mut sc = SC::from_check_unique([Face::from_iter([0, 2]), Face::from_iter([1, 2])]);
+    sc.pinch(None, &Silent);
+    assert![is_maximal(&sc)];
+    assert_eq![sc.euler_characteristic(), 1];
+}
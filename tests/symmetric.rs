@@ -0,0 +1,151 @@
+// Checks that the invariants of complexes given by orbits of facets agree with those of the
+// complexes they generate, and that the invariant homology is as expected for some known actions.
+
+mod common;
+
+use std::sync::atomic::AtomicBool;
+
+use common::{facets, trimmed};
+use itertools::Itertools;
+use sc_simplify::generators::{independence_complex, projective_plane, sphere, torus};
+use sc_simplify::io::{format_permutation, parse_permutation};
+use sc_simplify::progress_indicator::Silent;
+use sc_simplify::{Face, SymmetricComplex, partial_bijection_complex, partial_bijection_symmetric};
+
+fn rotation(n: u32) -> Vec<u32> {
+    (0..n).map(|v| (v + 1) % n).collect()
+}
+
+fn reflection(n: u32) -> Vec<u32> {
+    (0..n).map(|v| (n - v) % n).collect()
+}
+
+#[test]
+fn partial_bijection_complexes_are_single_orbits() {
+    for (a, b) in [(1, 3), (2, 3), (3, 3), (3, 4), (4, 4), (4, 5)] {
+        let sym = partial_bijection_symmetric(a, b);
+        let sc = partial_bijection_complex(a, b);
+        assert_eq![sym.representatives().len(), 1];
+        assert_eq![facets(&sym.to_complex()), facets(&sc)];
+        assert_eq![sym.f_vector(), sc.f_vector()];
+        assert_eq![sym.euler_characteristic(), sc.euler_characteristic()];
+    }
+}
+
+#[test]
+fn orbits_of_given_facets() {
+    // The orbits of two triangles under the rotation of the 7-vertex torus
+    let sc = SymmetricComplex::new(
+        vec![rotation(7)],
+        [vec![0, 1, 3], vec![0, 1, 5], vec![2, 3, 5], vec![0, 1]].map(Face::from_iter),
+    );
+    assert_eq![sc.representatives(), [vec![0, 1, 3], vec![0, 1, 5]]];
+    assert_eq![facets(&sc.to_complex()), facets(&torus())];
+    assert_eq![sc.facet_count(), 14];
+    assert_eq![sc.vertex_count(), 7];
+
+    let empty = SymmetricComplex::new(vec![rotation(3)], []);
+    assert![empty.is_empty()];
+    assert_eq![empty.f_vector(), Vec::<u128>::new()];
+}
+
+#[test]
+fn invariant_homology() {
+    // Without symmetry, the invariant homology is the homology.
+    for sc in [torus(), projective_plane()] {
+        let sym = SymmetricComplex::new(Vec::new(), sc.iter().cloned());
+        for p in [2, 3] {
+            assert_eq![
+                sym.reduced_invariant_betti_numbers(p),
+                sc.reduced_betti_numbers(p)
+            ];
+        }
+    }
+
+    // Translations of the torus act trivially on homology.
+    let torus = SymmetricComplex::new(vec![rotation(7)], torus());
+    for p in [2, 3, 5] {
+        assert_eq![torus.reduced_invariant_betti_numbers(p), vec![0, 2, 1]];
+    }
+
+    // A rotation of a hexagon preserves its orientation, unlike a reflection.
+    let hexagon = (0..6)
+        .map(|v| Face::from_iter([v, (v + 1) % 6]))
+        .collect_vec();
+    let rotated = SymmetricComplex::new(vec![rotation(6)], hexagon.clone());
+    assert_eq![rotated.reduced_invariant_betti_numbers(5), vec![0, 1]];
+    let reflected = SymmetricComplex::new(vec![reflection(6)], hexagon);
+    assert_eq![reflected.reduced_invariant_betti_numbers(5), vec![0, 0]];
+    assert_eq![reflected.invariant_betti_numbers(5), vec![1, 0]];
+
+    // The cyclic permutation of the vertices of a simplex preserves the orientation of its
+    // boundary if it is even. The prime should not divide the order of the group.
+    for n in 4..8 {
+        let sc = SymmetricComplex::new(vec![rotation(n)], sphere(n - 2));
+        let mut betti = vec![0; n as usize - 1];
+        betti[n as usize - 2] = (n % 2) as usize;
+        assert_eq![sc.reduced_invariant_betti_numbers(11), betti];
+    }
+}
+
+#[test]
+fn nerves_of_orbits_preserve_homology() {
+    for n in 5..13 {
+        let cycle = (0..n).map(|v| (v, (v + 1) % n)).collect_vec();
+        let sc = independence_complex(n, &cycle);
+        let mut sym = SymmetricComplex::new(vec![rotation(n), reflection(n)], sc.iter().cloned());
+        assert_eq![sym.f_vector(), sc.f_vector()];
+        sym.nerve_reduce(None, &Silent);
+        assert_eq![
+            trimmed(sym.to_complex().reduced_betti_numbers(2)),
+            trimmed(sc.reduced_betti_numbers(2))
+        ];
+    }
+}
+
+#[test]
+fn nerves_of_orbits_are_nerves() {
+    // The nerves are labeled differently, so they are compared up to isomorphism by their
+    // numbers of faces, also after taking the nerve twice.
+    let cycle = (0..9).map(|v| (v, (v + 1) % 9)).collect_vec();
+    for (sym, sc) in [
+        (SymmetricComplex::new(vec![rotation(7)], torus()), torus()),
+        (
+            partial_bijection_symmetric(3, 4),
+            partial_bijection_complex(3, 4),
+        ),
+        (
+            SymmetricComplex::new(
+                vec![rotation(9), reflection(9)],
+                independence_complex(9, &cycle),
+            ),
+            independence_complex(9, &cycle),
+        ),
+    ] {
+        let (nerve, sc_nerve) = (sym.nerve(), sc.nerve());
+        assert_eq![nerve.f_vector(), sc_nerve.f_vector()];
+        assert_eq![nerve.vertex_count(), sc.len()];
+        assert_eq![nerve.nerve().f_vector(), sc_nerve.nerve().f_vector()];
+    }
+}
+
+#[test]
+fn interrupted_nerves_of_orbits() {
+    let cycle = (0..9).map(|v| (v, (v + 1) % 9)).collect_vec();
+    let sc = independence_complex(9, &cycle);
+    let mut sym = SymmetricComplex::new(vec![rotation(9), reflection(9)], sc.iter().cloned());
+    let interrupted = AtomicBool::new(true);
+    assert_eq![sym.nerve_reduce(Some(&interrupted), &Silent), 0];
+    assert_eq![facets(&sym.to_complex()), facets(&sc)];
+}
+
+#[test]
+fn permutations_in_cycle_notation() {
+    assert_eq![parse_permutation("(0 2)(1 3 4)"), vec![2, 3, 0, 4, 1]];
+    assert_eq![parse_permutation("(0,2)(1,3,4)"), vec![2, 3, 0, 4, 1]];
+    // The cycles are applied from left to right.
+    assert_eq![parse_permutation("(0 1)(1 2)"), vec![2, 0, 1]];
+    assert_eq![parse_permutation("()"), Vec::<u32>::new()];
+    assert_eq![format_permutation(&[2, 3, 0, 4, 1]), "(0 2)(1 3 4)"];
+    assert_eq![format_permutation(&[0, 1]), "()"];
+}
@@ -0,0 +1,159 @@
+// Checks integral homology and the recognition of wedges of spheres on the standard triangulations.
+
+mod common;
+
+use common::SC;
+use itertools::Itertools;
+use sc_simplify::homology::{AbelianGroup, Certificate, Wedge};
+use sc_simplify::progress_indicator::Silent;
+use sc_simplify::{Face, generators, partial_bijection_complex};
+
+fn group(rank: usize, torsion: &[u64]) -> AbelianGroup {
+    AbelianGroup {
+        rank,
+        torsion: torsion.to_vec(),
+    }
+}
+
+fn pinched(mut sc: SC) -> SC {
+    while sc.pinch(None, &Silent) > 0 {}
+    sc
+}
+
+#[test]
+fn integral_homology() {
+    assert_eq![
+        generators::torus().integral_homology(),
+        vec![group(1, &[]), group(2, &[]), group(1, &[])]
+    ];
+    assert_eq![
+        generators::klein_bottle().integral_homology(),
+        vec![group(1, &[]), group(1, &[2]), group(0, &[])]
+    ];
+    assert_eq![
+        generators::projective_plane().reduced_integral_homology(),
+        vec![group(0, &[]), group(0, &[2]), group(0, &[])]
+    ];
+    assert_eq![
+        pinched(generators::lens_space(5, 2)).reduced_integral_homology(),
+        vec![group(0, &[]), group(0, &[5]), group(0, &[]), group(1, &[])]
+    ];
+    assert_eq![
+        generators::complex_projective_plane()
+            .reduced_integral_homology()
+            .iter()
+            .map(|h| h.rank)
+            .collect_vec(),
+        [0, 0, 1, 0, 1]
+    ];
+}
+
+#[test]
+fn universal_coefficients() {
+    // The Betti number over F_p in dimension `k` counts the free summands in dimension `k` and
+    // the cyclic summands of order divisible by p in dimensions `k` and `k - 1`.
+    for sc in [
+        generators::klein_bottle(),
+        generators::projective_plane(),
+        pinched(generators::lens_space(7, 3)),
+        pinched(partial_bijection_complex(5, 5)),
+    ] {
+        let homology = sc.integral_homology();
+        for p in [2, 3, 5, 7] {
+            let divisible = |k: usize| {
+                homology
+                    .get(k)
+                    .map_or(0, |h| h.torsion.iter().filter(|t| *t % p == 0).count())
+            };
+            let betti = (0..homology.len())
+                .map(|k| homology[k].rank + divisible(k) + k.checked_sub(1).map_or(0, divisible))
+                .collect_vec();
+            assert_eq![betti, sc.betti_numbers(p as u32)];
+        }
+    }
+}
+
+#[test]
+fn wedges() {
+    for d in 1..5 {
+        let wedge = generators::sphere(d).wedge_of_spheres().unwrap();
+        assert_eq![(wedge.count, wedge.dimension), (1, d as usize)];
+    }
+    assert_eq![
+        generators::sphere(3).wedge_of_spheres(),
+        Some(Wedge {
+            count: 1,
+            dimension: 3,
+            certificate: Certificate::SimplexBoundary
+        })
+    ];
+
+    // The chessboard complex of a 4 × 4 board is a wedge of 15 spheres of dimension 2.
+    let wedge = pinched(partial_bijection_complex(4, 4))
+        .wedge_of_spheres()
+        .unwrap();
+    assert_eq![
+        wedge,
+        Wedge {
+            count: 15,
+            dimension: 2,
+            certificate: Certificate::SimplyConnected
+        }
+    ];
+
+    // The 1-skeleton of a tetrahedron is a wedge of 3 circles.
+    let graph = SC::from_check_unique(
+        (0..4)
+            .tuple_combinations()
+            .map(|(v, w)| Face::from_iter([v, w])),
+    );
+    assert_eq![
+        graph.wedge_of_spheres(),
+        Some(Wedge {
+            count: 3,
+            dimension: 1,
+            certificate: Certificate::Graph {
+                vertices: 4,
+                edges: 6
+            }
+        })
+    ];
+}
+
+#[test]
+fn contractible() {
+    let simplex = SC::from_check_unique([Face::from_iter(0..5)]);
+    assert_eq![
+        simplex.wedge_of_spheres().unwrap().certificate,
+        Certificate::Collapsible
+    ];
+    // Neither collapses, but both are simply connected and acyclic.
+    for sc in [generators::dunce_hat(), generators::bings_house()] {
+        assert_eq![
+            sc.wedge_of_spheres(),
+            Some(Wedge {
+                count: 0,
+                dimension: 0,
+                certificate: Certificate::SimplyConnected
+            })
+        ];
+    }
+}
+
+#[test]
+fn not_recognized() {
+    let disconnected = SC::from_check_unique([Face::from_iter([0, 1]), Face::from_iter([2, 3])]);
+    for sc in [
+        SC::default(),
+        disconnected,
+        generators::torus(),
+        generators::projective_plane(),
+        generators::complex_projective_plane(),
+        pinched(generators::lens_space(5, 2)),
+        pinched(generators::poincare_sphere()),
+        // The chessboard complex of a 5 × 5 board has 3-torsion in its homology.
+        pinched(partial_bijection_complex(5, 5)),
+    ] {
+        assert_eq![sc.wedge_of_spheres(), None];
+    }
+}